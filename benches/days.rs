@@ -0,0 +1,116 @@
+use std::hint::black_box;
+
+use aoc_2024::{
+    day_01::Day01,
+    day_02::{Day02, Report},
+    day_03::Day03,
+    day_04::{
+        aho_corasick,
+        word_search::{search, Directions},
+        Day04,
+    },
+    day_05::Day05,
+    day_06::Day06,
+    day_07::Day07,
+    day_08::Day08,
+    day_09::Day09,
+    day_10::Day10,
+    day_11::Day11,
+    registry,
+    solution::Solution,
+    utils::{lcg::Lcg, Grid},
+};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+/// Benchmark the generator and both parts of a day separately on the real puzzle input.
+///
+/// Days without an input in `input/2024` are skipped.
+fn bench_day<S: Solution>(c: &mut Criterion) {
+    let Ok(input) = registry::read_input(S::DAY) else {
+        eprintln!(
+            "Day {}: skipped, {} not found",
+            S::DAY,
+            registry::input_path(S::DAY).display()
+        );
+        return;
+    };
+    let input = input.as_str();
+
+    let parsed = S::parse(input).expect("the puzzle input should be valid");
+
+    let mut group = c.benchmark_group(format!("day{:02}", S::DAY));
+    group.bench_function("parse", |b| b.iter(|| S::parse(black_box(input))));
+    group.bench_function("part1", |b| b.iter(|| S::part1(black_box(&parsed))));
+    group.bench_function("part2", |b| b.iter(|| S::part2(black_box(&parsed))));
+    group.finish();
+}
+
+fn days(c: &mut Criterion) {
+    bench_day::<Day01>(c);
+    bench_day::<Day02>(c);
+    bench_day::<Day03>(c);
+    bench_day::<Day04>(c);
+    bench_day::<Day05>(c);
+    bench_day::<Day06>(c);
+    bench_day::<Day07>(c);
+    bench_day::<Day08>(c);
+    bench_day::<Day09>(c);
+    bench_day::<Day10>(c);
+    bench_day::<Day11>(c);
+}
+
+/// Compare the Problem Dampener implementations of day 2 on long reports, which only become safe
+/// by removing a spike in the middle.
+fn day02_dampener(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day02_dampener");
+
+    for len in [100, 1_000, 10_000] {
+        let mut levels = (0..len as i64).map(|i| i * 2).collect::<Vec<_>>();
+        levels[len / 2] += 100;
+        let report = Report::new(levels);
+
+        group.bench_with_input(BenchmarkId::new("naive", len), &report, |b, report| {
+            b.iter(|| report.is_safe_after_dampening_naive())
+        });
+        group.bench_with_input(BenchmarkId::new("linear", len), &report, |b, report| {
+            b.iter(|| report.check_dampened())
+        });
+        group.bench_with_input(BenchmarkId::new("removals", len), &report, |b, report| {
+            b.iter(|| report.is_safe_with_removals(1))
+        });
+    }
+
+    group.finish();
+}
+
+/// Compare the word search with the Aho–Corasick scan for part 1 of day 4 on generated square
+/// grids of growing size.
+fn day04_xmas(c: &mut Criterion) {
+    let mut group = c.benchmark_group("day04_xmas");
+
+    for size in [100, 500, 1_000] {
+        // the grids are the same for every run
+        let mut rng = Lcg::new(4);
+        let cells = (0..size * size)
+            .map(|_| *rng.pick(&['X', 'M', 'A', 'S']))
+            .collect();
+        let grid = Grid::new(cells, size);
+
+        group.bench_with_input(BenchmarkId::new("word_search", size), &grid, |b, grid| {
+            b.iter(|| search(grid, &["XMAS"], Directions::ALL).len())
+        });
+        group.bench_with_input(BenchmarkId::new("aho_corasick", size), &grid, |b, grid| {
+            b.iter(|| aho_corasick::count(grid, &["XMAS"]))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    // some parts take close to a second per iteration, so keep the sample count at criterion's minimum
+    config = Criterion::default().sample_size(10);
+    targets = days, day02_dampener, day04_xmas
+}
+criterion_main!(benches);
@@ -0,0 +1,151 @@
+use std::{collections::BTreeMap, error::Error, fs, io, path::Path};
+
+use toml::{Table, Value};
+
+use crate::{
+    registry::{self, Runnable},
+    utils::ParseError,
+};
+
+/// Default location of the accepted answers, relative to the repository root.
+pub const ANSWERS_PATH: &str = "answers/2024.toml";
+
+/// Accepted answers, keyed by day and part.
+///
+/// The file contains one table per day with an entry per part, e.g.
+///
+/// ```toml
+/// [day1]
+/// part1 = 11
+/// part2 = "31"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Answers {
+    answers: BTreeMap<(u32, u32), String>,
+}
+
+impl Answers {
+    pub fn parse(input: &str) -> Result<Self, Box<dyn Error>> {
+        let table = input.parse::<Table>()?;
+
+        let mut answers = BTreeMap::new();
+
+        for (day_key, parts) in &table {
+            let day = number(day_key, "day")?;
+            let Value::Table(parts) = parts else {
+                return Err(format!("`{day_key}` has to be a table of parts").into());
+            };
+
+            for (part_key, answer) in parts {
+                let part = number(part_key, "part")?;
+                let answer = match answer {
+                    Value::Integer(answer) => answer.to_string(),
+                    Value::String(answer) => answer.clone(),
+                    _ => {
+                        return Err(format!(
+                            "`{day_key}.{part_key}` has to be an integer or a string"
+                        )
+                        .into())
+                    }
+                };
+
+                answers.insert((day, part), answer);
+            }
+        }
+
+        Ok(Answers { answers })
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<dyn Error>> {
+        let path = path.as_ref();
+        let input = fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+
+        Self::parse(&input).map_err(|e| format!("{}: {e}", path.display()).into())
+    }
+
+    pub fn get(&self, day: u32, part: u32) -> Option<&str> {
+        self.answers.get(&(day, part)).map(String::as_str)
+    }
+}
+
+fn number(key: &str, prefix: &str) -> Result<u32, Box<dyn Error>> {
+    key.strip_prefix(prefix)
+        .and_then(|n| n.parse().ok())
+        .ok_or_else(|| format!("expected a key like `{prefix}1`, found `{key}`").into())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong {
+        expected: String,
+        actual: String,
+    },
+    /// No accepted answer has been recorded yet.
+    Unknown {
+        actual: String,
+    },
+    /// The puzzle input does not exist, so the part was not run.
+    MissingInput,
+    /// The puzzle input exists, but the generator rejected it.
+    InvalidInput(ParseError),
+}
+
+impl Verdict {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Verdict::Wrong { .. } | Verdict::InvalidInput(_))
+    }
+}
+
+/// Run both parts of `day` against its input in `input/2024` and compare them with `answers`.
+pub fn verify(day: &dyn Runnable, answers: &Answers) -> io::Result<[Verdict; 2]> {
+    let input = match registry::read_input(day.day()) {
+        Ok(input) => input,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            return Ok([Verdict::MissingInput, Verdict::MissingInput])
+        }
+        Err(e) => return Err(e),
+    };
+
+    let verdict = |part: u32, result: Result<String, ParseError>| {
+        let actual = match result {
+            Ok(actual) => actual,
+            Err(e) => return Verdict::InvalidInput(e),
+        };
+
+        match answers.get(day.day(), part) {
+            Some(expected) if expected == actual => Verdict::Correct,
+            Some(expected) => Verdict::Wrong {
+                expected: expected.to_string(),
+                actual,
+            },
+            None => Verdict::Unknown { actual },
+        }
+    };
+
+    Ok([verdict(1, day.part1(&input)), verdict(2, day.part2(&input))])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Answers;
+
+    #[test]
+    fn test_parse() {
+        let answers =
+            Answers::parse("[day1]\npart1 = 11\npart2 = \"31\"\n\n[day12]\npart1 = 3").unwrap();
+
+        assert_eq!(answers.get(1, 1), Some("11"));
+        assert_eq!(answers.get(1, 2), Some("31"));
+        assert_eq!(answers.get(12, 1), Some("3"));
+        assert_eq!(answers.get(12, 2), None);
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(Answers::parse("[first]\npart1 = 11").is_err());
+        assert!(Answers::parse("[day1]\none = 11").is_err());
+        assert!(Answers::parse("[day1]\npart1 = 1.5").is_err());
+        assert!(Answers::parse("day1 = 11").is_err());
+    }
+}
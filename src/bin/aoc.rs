@@ -0,0 +1,256 @@
+use std::{
+    error::Error,
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+    process::ExitCode,
+    time::Instant,
+};
+
+use aoc_2024::{
+    answers::{self, Answers, Verdict},
+    day_03,
+    day_04::{render::Highlight, Day04},
+    registry::{self, Runnable},
+    solution::Solution,
+};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+
+/// Run the Advent of Code 2024 solutions without cargo-aoc.
+#[derive(Debug, Parser)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Run one day (or all days) against a puzzle input.
+    Run(RunArgs),
+    /// Check all days against the accepted answers for the inputs in `input/2024`.
+    Verify(VerifyArgs),
+    /// List all implemented days.
+    List,
+    /// Show which multiplications of day 3 were applied or skipped, and why.
+    Trace(TraceArgs),
+    /// Show the matches of day 4 in the word search, blanking every other letter.
+    Highlight(HighlightArgs),
+}
+
+#[derive(Debug, Args)]
+struct RunArgs {
+    /// The day to run.
+    #[arg(long, required_unless_present = "all", conflicts_with = "all")]
+    day: Option<u32>,
+
+    /// The part to run. Runs both parts if omitted.
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..=2))]
+    part: Option<u32>,
+
+    /// Run every implemented day against `input/2024/day<N>.txt`.
+    #[arg(long, conflicts_with_all = ["input", "stdin"])]
+    all: bool,
+
+    #[command(flatten)]
+    input: InputArgs,
+}
+
+#[derive(Debug, Args)]
+struct InputArgs {
+    /// Path to the puzzle input. Defaults to `input/2024/day<N>.txt`.
+    #[arg(long, conflicts_with = "stdin")]
+    input: Option<PathBuf>,
+
+    /// Read the puzzle input from stdin.
+    #[arg(long)]
+    stdin: bool,
+}
+
+fn read_input(args: &InputArgs, day: u32) -> Result<String, Box<dyn Error>> {
+    if args.stdin {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+        return Ok(input);
+    }
+
+    let path = args
+        .input
+        .clone()
+        .unwrap_or_else(|| registry::input_path(day));
+    fs::read_to_string(&path).map_err(|e| format!("{}: {e}", path.display()).into())
+}
+
+#[derive(Debug, Args)]
+struct VerifyArgs {
+    /// Path to the accepted answers.
+    #[arg(long, default_value = answers::ANSWERS_PATH)]
+    answers: PathBuf,
+}
+
+#[derive(Debug, Args)]
+struct TraceArgs {
+    /// How to print the trace.
+    #[arg(long, value_enum, default_value_t = TraceFormat::Terminal)]
+    format: TraceFormat,
+
+    #[command(flatten)]
+    input: InputArgs,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum TraceFormat {
+    /// The corrupted memory with colored instructions.
+    Terminal,
+    /// Every instruction with its span, whether it was applied and the running totals.
+    Json,
+}
+
+#[derive(Debug, Args)]
+struct HighlightArgs {
+    /// Show the XMAS words of part 1 or the X-MAS crosses of part 2.
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u32).range(1..=2))]
+    part: u32,
+
+    /// Color the words by their direction and dim the blanks.
+    #[arg(long)]
+    color: bool,
+
+    #[command(flatten)]
+    input: InputArgs,
+}
+
+fn run_day(day: &dyn Runnable, parts: &[u32], input: &str) -> Result<(), Box<dyn Error>> {
+    // cargo-aoc strips trailing newlines as well, so the generators never see them
+    let input = input.trim_end_matches('\n');
+
+    for &part in parts {
+        let start = Instant::now();
+        let answer = match part {
+            1 => day.part1(input),
+            2 => day.part2(input),
+            _ => return Err(format!("unknown part {part}").into()),
+        }
+        .map_err(|e| format!("Day {} - Part {part}: {e}", day.day()))?;
+        let elapsed = start.elapsed();
+
+        println!(
+            "Day {} - Part {part}: {answer}\n\telapsed: {elapsed:?}",
+            day.day()
+        );
+    }
+
+    Ok(())
+}
+
+fn run(args: &RunArgs) -> Result<(), Box<dyn Error>> {
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    if args.all {
+        for day in registry::DAYS {
+            let path = registry::input_path(day.day());
+            if !path.exists() {
+                eprintln!("Day {}: skipped, {} not found", day.day(), path.display());
+                continue;
+            }
+
+            run_day(*day, &parts, &read_input(&args.input, day.day())?)?;
+        }
+
+        return Ok(());
+    }
+
+    let number = args.day.ok_or("no day given")?;
+    let day = registry::find(number).ok_or_else(|| format!("day {number} is not implemented"))?;
+
+    run_day(day, &parts, &read_input(&args.input, number)?)
+}
+
+fn verify(args: &VerifyArgs) -> Result<(), Box<dyn Error>> {
+    let answers = Answers::load(&args.answers)?;
+
+    let mut failures = 0;
+
+    for day in registry::DAYS {
+        for (part, verdict) in (1..).zip(answers::verify(*day, &answers)?) {
+            let message = match &verdict {
+                Verdict::Correct => "ok".to_string(),
+                Verdict::Wrong { expected, actual } => {
+                    format!("WRONG, expected {expected} but got {actual}")
+                }
+                Verdict::Unknown { actual } => format!("{actual} (no accepted answer yet)"),
+                Verdict::MissingInput => "skipped, no input".to_string(),
+                Verdict::InvalidInput(e) => format!("INVALID INPUT, {e}"),
+            };
+
+            println!("Day {} - Part {part}: {message}", day.day());
+
+            if verdict.is_failure() {
+                failures += 1;
+            }
+        }
+    }
+
+    if failures > 0 {
+        return Err(format!("{failures} part(s) failed verification").into());
+    }
+
+    Ok(())
+}
+
+fn trace(args: &TraceArgs) -> Result<(), Box<dyn Error>> {
+    let input = read_input(&args.input, 3)?;
+    let trace = day_03::trace::trace(input.trim_end_matches('\n'));
+
+    match args.format {
+        TraceFormat::Terminal => println!("{}", trace.to_terminal()),
+        TraceFormat::Json => println!("{}", trace.to_json()),
+    }
+
+    Ok(())
+}
+
+fn highlight(args: &HighlightArgs) -> Result<(), Box<dyn Error>> {
+    let input = read_input(&args.input, 4)?;
+    let input = Day04::parse(input.trim_end_matches('\n'))?;
+
+    let highlight = match args.part {
+        1 => Highlight::xmas(input.grid()),
+        _ => Highlight::x_mas(input.grid()),
+    };
+
+    if args.color {
+        println!("{}", highlight.to_terminal());
+    } else {
+        println!("{}", highlight.to_plain());
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    let result = match &cli.command {
+        Command::Run(args) => run(args),
+        Command::Verify(args) => verify(args),
+        Command::List => {
+            for day in registry::DAYS {
+                println!("Day {}", day.day());
+            }
+            Ok(())
+        }
+        Command::Trace(args) => trace(args),
+        Command::Highlight(args) => highlight(args),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
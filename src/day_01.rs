@@ -1,78 +1,537 @@
-use std::collections::HashMap;
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BinaryHeap, HashMap},
+    error::Error,
+    fmt::Display,
+    fs::{self, File},
+    io::{self, BufRead, BufReader, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+};
 
 use aoc_runner_derive::{aoc, aoc_generator};
 
+use crate::{
+    solution::Solution,
+    utils::{
+        parse::{parse_columns, parse_table},
+        ParseError,
+    },
+};
+
+/// Columns of numbers, all of the same length. The puzzle itself only looks at the first two.
 #[derive(Clone, Debug, PartialEq, Eq)]
-struct Lists(Vec<i64>, Vec<i64>);
+pub struct Lists(Vec<Vec<i64>>);
 
-#[aoc_generator(day1)]
-fn generator_day1(input: &str) -> Lists {
-    let mut left = vec![];
-    let mut right = vec![];
-
-    input.split("\n").for_each(|line| {
-        let parts = line.split_whitespace().collect::<Vec<_>>();
-        left.push(
-            parts
-                .first()
-                .expect("Something went wrong")
-                .parse()
-                .expect("Something went wrong"),
+impl Lists {
+    /// Panics if there are fewer than two columns or they are not all of the same length.
+    pub fn new(columns: Vec<Vec<i64>>) -> Self {
+        assert!(columns.len() >= 2, "there have to be at least two columns");
+        assert!(
+            columns
+                .windows(2)
+                .all(|pair| pair[0].len() == pair[1].len()),
+            "all columns have to be of the same length"
         );
-        right.push(
-            parts
-                .get(1)
-                .expect("Something went wrong")
-                .parse()
-                .expect("Something went wrong"),
-        );
-    });
 
-    Lists(left, right)
+        Lists(columns)
+    }
+
+    /// Parse two or more whitespace separated columns. The first line decides how many columns
+    /// every other line has to have.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let first = input.lines().next().unwrap_or(input);
+        let width = first.split_whitespace().count();
+
+        // both parts need a left and a right list
+        if width < 2 {
+            return Err(ParseError::missing(input, first, "a number"));
+        }
+
+        let columns = parse_columns(input, width, "a number")?;
+
+        Ok(Lists(columns))
+    }
+
+    pub fn columns(&self) -> &[Vec<i64>] {
+        &self.0
+    }
+
+    pub fn left(&self) -> &[i64] {
+        &self.0[0]
+    }
+
+    pub fn right(&self) -> &[i64] {
+        &self.0[1]
+    }
+
+    /// Compare every column with every other column (and itself) using `metric`.
+    pub fn similarity_matrix<M: Metric>(&self, metric: &M) -> Vec<Vec<M::Output>> {
+        let counts = self
+            .0
+            .iter()
+            .map(|column| counts(column))
+            .collect::<Vec<_>>();
+
+        counts
+            .iter()
+            .map(|a| counts.iter().map(|b| metric.compare(a, b)).collect())
+            .collect()
+    }
+}
+
+#[aoc_generator(day1)]
+fn generator_day1(input: &str) -> Result<Lists, ParseError> {
+    Lists::parse(input)
 }
 
 #[aoc(day1, part1)]
 fn part1(input: &Lists) -> i64 {
-    let Lists(mut left, mut right) = input.clone();
+    pairing(input).total()
+}
 
-    assert_eq!(left.len(), right.len());
+/// One row of the sorted lists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Pair {
+    pub left: i64,
+    pub right: i64,
+    pub distance: i64,
+}
 
-    left.sort();
-    right.sort();
+/// Both lists sorted and paired up row by row, as used by [`part1`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pairing {
+    pub pairs: Vec<Pair>,
+}
 
-    let mut sum = 0;
+impl Pairing {
+    pub fn total(&self) -> i64 {
+        self.pairs.iter().map(|pair| pair.distance).sum()
+    }
 
-    for i in 0..left.len() {
-        sum += (left[i] - right[i]).abs();
+    pub fn max_distance(&self) -> Option<i64> {
+        self.pairs.iter().map(|pair| pair.distance).max()
     }
 
-    sum
+    /// Median of all distances. For an even number of pairs this is the mean of the two middle
+    /// distances.
+    pub fn median(&self) -> Option<f64> {
+        let mut distances = self
+            .pairs
+            .iter()
+            .map(|pair| pair.distance)
+            .collect::<Vec<_>>();
+        distances.sort_unstable();
+
+        let middle = distances.len() / 2;
+        match distances.len() {
+            0 => None,
+            len if len % 2 == 1 => Some(distances[middle] as f64),
+            _ => Some((distances[middle - 1] + distances[middle]) as f64 / 2.0),
+        }
+    }
+
+    /// How many pairs there are per distance.
+    pub fn histogram(&self) -> BTreeMap<i64, usize> {
+        let mut histogram = BTreeMap::new();
+
+        for pair in &self.pairs {
+            *histogram.entry(pair.distance).or_default() += 1;
+        }
+
+        histogram
+    }
+}
+
+pub fn pairing(input: &Lists) -> Pairing {
+    let mut left = input.left().to_vec();
+    let mut right = input.right().to_vec();
+
+    left.sort();
+    right.sort();
+
+    let pairs = left
+        .into_iter()
+        .zip(right)
+        .map(|(left, right)| Pair {
+            left,
+            right,
+            distance: (left - right).abs(),
+        })
+        .collect();
+
+    Pairing { pairs }
 }
 
 #[aoc(day1, part2)]
 fn part2(input: &Lists) -> i64 {
-    let Lists(left, right) = input;
+    Weighted.compare(&counts(input.left()), &counts(input.right()))
+}
+
+/// How often each number occurs in a list.
+pub type Counts = HashMap<i64, i64>;
 
+pub fn counts(list: &[i64]) -> Counts {
     let mut map = HashMap::<i64, i64>::new();
 
-    for i in right {
+    for i in list {
         let current = map.get(i).cloned().unwrap_or_default();
         map.insert(*i, current + 1);
     }
 
-    let mut sum = 0;
+    map
+}
+
+/// How often a number of the left list occurs in both lists, and what it adds to the similarity
+/// score.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Occurrences {
+    pub left: i64,
+    pub right: i64,
+    pub score: i64,
+}
+
+/// The contributions to [`part2`] per distinct number of the left list. Their scores add up to the
+/// similarity score.
+pub fn occurrences(input: &Lists) -> BTreeMap<i64, Occurrences> {
+    let right = counts(input.right());
+
+    counts(input.left())
+        .into_iter()
+        .map(|(num, left)| {
+            let right = right.get(&num).cloned().unwrap_or_default();
+            let occurrences = Occurrences {
+                left,
+                right,
+                score: num * left * right,
+            };
+
+            (num, occurrences)
+        })
+        .collect()
+}
+
+/// A way to compare two lists, given how often each number occurs in them.
+pub trait Metric {
+    type Output;
+
+    fn compare(&self, a: &Counts, b: &Counts) -> Self::Output;
+}
+
+/// The similarity score of the puzzle: every number times how often it occurs in both lists.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Weighted;
+
+impl Metric for Weighted {
+    type Output = i64;
+
+    fn compare(&self, a: &Counts, b: &Counts) -> i64 {
+        let mut sum = 0;
+
+        for (num, count) in a {
+            let occurences = b.get(num).cloned().unwrap_or_default();
+            sum += *num * count * occurences;
+        }
+
+        sum
+    }
+}
+
+/// Size of the intersection of the distinct numbers, relative to the size of their union. Two
+/// empty lists are considered equal.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Jaccard;
+
+impl Metric for Jaccard {
+    type Output = f64;
+
+    fn compare(&self, a: &Counts, b: &Counts) -> f64 {
+        let intersection = a.keys().filter(|num| b.contains_key(num)).count();
+        let union = a.len() + b.len() - intersection;
+
+        if union == 0 {
+            return 1.0;
+        }
+
+        intersection as f64 / union as f64
+    }
+}
+
+/// Number of elements both lists have in common, counting duplicates.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Intersection;
+
+impl Metric for Intersection {
+    type Output = i64;
+
+    fn compare(&self, a: &Counts, b: &Counts) -> i64 {
+        a.iter()
+            .map(|(num, count)| *count.min(b.get(num).unwrap_or(&0)))
+            .sum()
+    }
+}
+
+/// Total distance and similarity score of two lists, as computed by [`part1`] and [`part2`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Totals {
+    pub distance: i64,
+    pub similarity: i64,
+}
+
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl From<io::Error> for StreamError {
+    fn from(value: io::Error) -> Self {
+        StreamError::Io(value)
+    }
+}
+
+impl From<ParseError> for StreamError {
+    fn from(value: ParseError) -> Self {
+        StreamError::Parse(value)
+    }
+}
+
+impl Display for StreamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StreamError::Io(e) => write!(f, "{e}"),
+            StreamError::Parse(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl Error for StreamError {}
+
+/// How many runs are merged at once. Every merge keeps one file per run open.
+const MAX_FAN_IN: usize = 16;
+
+/// Compute both totals for lists that do not fit into memory.
+///
+/// The two columns are read from `input` in chunks of `run_len` lines. Each chunk is sorted and
+/// spilled to a temporary file. The sorted runs of each column are then merged, at most
+/// [`MAX_FAN_IN`] at a time, until a single run is left. At most `run_len` values per column are
+/// held in memory at once.
+pub fn stream_totals(input: impl Read, run_len: usize) -> Result<Totals, StreamError> {
+    assert!(run_len > 0, "runs have to hold at least one value");
+
+    let dir = tempfile::tempdir()?;
+
+    let mut left_runs = vec![];
+    let mut right_runs = vec![];
+
+    let mut left = Vec::with_capacity(run_len);
+    let mut right = Vec::with_capacity(run_len);
+
+    for (n, line) in BufReader::new(input).lines().enumerate() {
+        let line = line?;
+
+        let [l, r] = parse_table::<i64, 2>(&line, "a number")
+            .map_err(|e| e.offset_lines(n))?
+            .pop()
+            .ok_or_else(|| ParseError::missing(&line, &line, "a number").offset_lines(n))?;
+        left.push(l);
+        right.push(r);
+
+        if left.len() == run_len {
+            left_runs.push(spill(dir.path().join(format!("left-{n}")), &mut left)?);
+            right_runs.push(spill(dir.path().join(format!("right-{n}")), &mut right)?);
+        }
+    }
+
+    if !left.is_empty() {
+        left_runs.push(spill(dir.path().join("left"), &mut left)?);
+        right_runs.push(spill(dir.path().join("right"), &mut right)?);
+    }
+
+    let left = merge_all(dir.path(), "left", left_runs)?;
+    let right = merge_all(dir.path(), "right", right_runs)?;
+
+    let mut distance = 0;
+    for pair in Run::open(&left)?.zip(Run::open(&right)?) {
+        let (l, r) = (pair.0?, pair.1?);
+        distance += (l - r).abs();
+    }
+
+    // both columns are sorted, so equal values can be counted in lockstep
+    let mut similarity = 0;
+    let mut left = Run::open(&left)?;
+    let mut right = Run::open(&right)?;
+
+    let mut l = left.next().transpose()?;
+    let mut r = right.next().transpose()?;
+
+    while let Some(num) = l {
+        let mut left_count = 0;
+        while l == Some(num) {
+            left_count += 1;
+            l = left.next().transpose()?;
+        }
+
+        while r.is_some_and(|r| r < num) {
+            r = right.next().transpose()?;
+        }
+
+        let mut right_count = 0;
+        while r == Some(num) {
+            right_count += 1;
+            r = right.next().transpose()?;
+        }
+
+        similarity += num * left_count * right_count;
+    }
+
+    Ok(Totals {
+        distance,
+        similarity,
+    })
+}
+
+/// Sort `values` and write them to `path`, leaving `values` empty.
+fn spill(path: PathBuf, values: &mut Vec<i64>) -> io::Result<PathBuf> {
+    values.sort_unstable();
+    write_run(path, values.drain(..).map(Ok))
+}
+
+fn write_run(
+    path: PathBuf,
+    values: impl IntoIterator<Item = io::Result<i64>>,
+) -> io::Result<PathBuf> {
+    let mut writer = BufWriter::new(File::create(&path)?);
+    for value in values {
+        writer.write_all(&value?.to_le_bytes())?;
+    }
+    writer.flush()?;
+
+    Ok(path)
+}
+
+/// Merge sorted `runs` into a single one in `dir`, at most [`MAX_FAN_IN`] runs at a time. The
+/// merged runs are deleted.
+fn merge_all(dir: &Path, name: &str, mut runs: Vec<PathBuf>) -> io::Result<PathBuf> {
+    for generation in 0.. {
+        if runs.len() <= 1 {
+            break;
+        }
+
+        runs = runs
+            .chunks(MAX_FAN_IN)
+            .enumerate()
+            .map(|(i, chunk)| {
+                let path = dir.join(format!("{name}-merged-{generation}-{i}"));
+                let merged = write_run(path, Merge::new(chunk)?)?;
+
+                for run in chunk {
+                    fs::remove_file(run)?;
+                }
 
-    for num in left {
-        let occurences = map.get(num).cloned().unwrap_or_default();
-        sum += *num * occurences;
+                Ok(merged)
+            })
+            .collect::<io::Result<_>>()?;
     }
 
-    sum
+    match runs.pop() {
+        Some(run) => Ok(run),
+        None => write_run(dir.join(format!("{name}-empty")), []),
+    }
+}
+
+/// Reads the values of a sorted run one by one.
+struct Run(BufReader<File>);
+
+impl Run {
+    fn open(path: &Path) -> io::Result<Self> {
+        Ok(Run(BufReader::new(File::open(path)?)))
+    }
+}
+
+impl Iterator for Run {
+    type Item = io::Result<i64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut bytes = [0; 8];
+
+        match self.0.read_exact(&mut bytes) {
+            Ok(()) => Some(Ok(i64::from_le_bytes(bytes))),
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+/// k-way merge of sorted runs.
+struct Merge {
+    runs: Vec<Run>,
+    heap: BinaryHeap<Reverse<(i64, usize)>>,
+}
+
+impl Merge {
+    fn new(paths: &[PathBuf]) -> io::Result<Self> {
+        let mut runs = vec![];
+        let mut heap = BinaryHeap::new();
+
+        for (i, path) in paths.iter().enumerate() {
+            let mut run = Run::open(path)?;
+            if let Some(value) = run.next() {
+                heap.push(Reverse((value?, i)));
+            }
+            runs.push(run);
+        }
+
+        Ok(Merge { runs, heap })
+    }
+}
+
+impl Iterator for Merge {
+    type Item = io::Result<i64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((value, i)) = self.heap.pop()?;
+
+        match self.runs[i].next() {
+            Some(Ok(next)) => self.heap.push(Reverse((next, i))),
+            Some(Err(e)) => return Some(Err(e)),
+            None => {}
+        }
+
+        Some(Ok(value))
+    }
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u32 = 1;
+
+    type Input = Lists;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        generator_day1(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day_01::{part2, Lists};
+    use crate::{
+        day_01::{
+            occurrences, pairing, part2, stream_totals, Intersection, Jaccard, Lists, Occurrences,
+            Pair, StreamError, Totals, Weighted,
+        },
+        utils::{lcg::Lcg, ParseError},
+    };
 
     use super::{generator_day1, part1};
 
@@ -85,17 +544,17 @@ mod tests {
 
     #[test]
     fn test_generator_part1() {
-        let output = generator_day1(INPUT);
+        let output = generator_day1(INPUT).unwrap();
 
         assert_eq!(
             output,
-            Lists(vec![3, 4, 2, 1, 3, 3], vec![4, 3, 5, 3, 9, 3])
+            Lists(vec![vec![3, 4, 2, 1, 3, 3], vec![4, 3, 5, 3, 9, 3]])
         );
     }
 
     #[test]
     fn test_part1() {
-        let gen = generator_day1(INPUT);
+        let gen = generator_day1(INPUT).unwrap();
         let output = part1(&gen);
 
         assert_eq!(output, 11);
@@ -103,9 +562,205 @@ mod tests {
 
     #[test]
     fn test_part2() {
-        let gen = generator_day1(INPUT);
+        let gen = generator_day1(INPUT).unwrap();
         let output = part2(&gen);
 
         assert_eq!(output, 31);
     }
+
+    #[test]
+    fn test_pairing() {
+        let gen = generator_day1(INPUT).unwrap();
+        let pairs = pairing(&gen);
+
+        assert_eq!(
+            pairs.pairs[..2],
+            [
+                Pair {
+                    left: 1,
+                    right: 3,
+                    distance: 2
+                },
+                Pair {
+                    left: 2,
+                    right: 3,
+                    distance: 1
+                }
+            ]
+        );
+        assert_eq!(pairs.total(), 11);
+        assert_eq!(pairs.max_distance(), Some(5));
+        assert_eq!(pairs.median(), Some(1.5));
+        assert_eq!(
+            pairs.histogram().into_iter().collect::<Vec<_>>(),
+            vec![(0, 1), (1, 2), (2, 2), (5, 1)]
+        );
+
+        let empty = pairing(&Lists(vec![vec![], vec![]]));
+        assert_eq!(empty.max_distance(), None);
+        assert_eq!(empty.median(), None);
+    }
+
+    #[test]
+    fn test_occurrences() {
+        let gen = generator_day1(INPUT).unwrap();
+        let occurrences = occurrences(&gen);
+
+        assert_eq!(
+            occurrences[&3],
+            Occurrences {
+                left: 3,
+                right: 3,
+                score: 27
+            }
+        );
+        assert_eq!(occurrences[&1].right, 0);
+        assert_eq!(occurrences.len(), 4);
+        assert_eq!(occurrences.values().map(|o| o.score).sum::<i64>(), 31);
+    }
+
+    #[test]
+    fn test_similarity_matrix() {
+        let lists = Lists::parse("3 4 3\n4 3 3\n2 5 3\n1 3 3\n3 9 3\n3 3 3").unwrap();
+        assert_eq!(lists.columns().len(), 3);
+
+        let weighted = lists.similarity_matrix(&Weighted);
+        assert_eq!(weighted[0][1], 31);
+        assert_eq!(weighted[1][0], 31);
+        assert_eq!(weighted[0][0], 34);
+
+        let jaccard = lists.similarity_matrix(&Jaccard);
+        assert_eq!(jaccard[0][1], 2.0 / 6.0);
+        assert_eq!(jaccard[1][2], 1.0 / 4.0);
+        assert_eq!(jaccard[2][2], 1.0);
+
+        assert_eq!(
+            lists.similarity_matrix(&Intersection),
+            vec![vec![6, 4, 3], vec![4, 6, 3], vec![3, 3, 6]]
+        );
+
+        let empty = Lists::new(vec![vec![], vec![]]);
+        assert_eq!(empty.similarity_matrix(&Jaccard)[0][1], 1.0);
+    }
+
+    #[test]
+    fn test_generator_malformed() {
+        assert_eq!(
+            generator_day1("3   4\n4   x"),
+            Err(ParseError::InvalidToken {
+                line: 2,
+                column: 5,
+                token: "x".into(),
+                expected: "a number"
+            })
+        );
+        assert_eq!(
+            generator_day1("3   4\n4"),
+            Err(ParseError::MissingToken {
+                line: 2,
+                column: 2,
+                expected: "a number"
+            })
+        );
+        assert_eq!(
+            generator_day1("3\n4"),
+            Err(ParseError::MissingToken {
+                line: 1,
+                column: 2,
+                expected: "a number"
+            })
+        );
+        assert_eq!(
+            generator_day1("3 4\n4 3 1"),
+            Err(ParseError::InvalidToken {
+                line: 2,
+                column: 5,
+                token: "1".into(),
+                expected: "end of line"
+            })
+        );
+        assert!(generator_day1("").is_err());
+        assert!(generator_day1("3 4 5\n4 3 1").is_ok());
+    }
+
+    #[test]
+    #[should_panic = "at least two columns"]
+    fn test_lists_single_column() {
+        Lists::new(vec![vec![1, 2, 3]]);
+    }
+
+    #[test]
+    fn test_stream_totals() {
+        for run_len in [1, 2, 4, 100] {
+            let totals = stream_totals(INPUT.as_bytes(), run_len).unwrap();
+
+            assert_eq!(
+                totals,
+                Totals {
+                    distance: 11,
+                    similarity: 31
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn test_stream_totals_match_in_memory() {
+        // many runs with lots of duplicates between them
+        let mut rng = Lcg::new(42);
+        let input = (0..1000)
+            .map(|_| format!("{}   {}", rng.below(1000), rng.below(1000)))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let lists = generator_day1(&input).unwrap();
+        let totals = stream_totals(input.as_bytes(), 64).unwrap();
+
+        assert_eq!(totals.distance, part1(&lists));
+        assert_eq!(totals.similarity, part2(&lists));
+    }
+
+    #[test]
+    fn test_stream_totals_many_runs() {
+        // far more runs than can be merged at once, so they are merged in several generations
+        let input = (0..600)
+            .map(|i| format!("{}   {}", i % 37, (i * 7) % 41))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let lists = generator_day1(&input).unwrap();
+        let totals = stream_totals(input.as_bytes(), 1).unwrap();
+
+        assert_eq!(totals.distance, part1(&lists));
+        assert_eq!(totals.similarity, part2(&lists));
+        assert_eq!(
+            stream_totals("".as_bytes(), 1).unwrap(),
+            Totals {
+                distance: 0,
+                similarity: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_stream_totals_malformed() {
+        let Err(StreamError::Parse(err)) = stream_totals("3   4\n\n4   x".as_bytes(), 1) else {
+            panic!("expected a parse error");
+        };
+
+        assert_eq!(
+            err,
+            ParseError::MissingToken {
+                line: 2,
+                column: 1,
+                expected: "a number"
+            }
+        );
+
+        let Err(StreamError::Parse(err)) = stream_totals("3   4\n4   x".as_bytes(), 1) else {
+            panic!("expected a parse error");
+        };
+
+        assert_eq!((err.line(), err.column()), (2, 5));
+    }
 }
@@ -1,58 +1,365 @@
+use std::fmt::Display;
+
 use aoc_runner_derive::{aoc, aoc_generator};
 
+use crate::{
+    solution::Solution,
+    utils::{parse::parse_rows, ParseError},
+};
+
+/// A report with its levels.
+///
+/// Reports with fewer than two levels have no adjacent levels that could break any rule, so they
+/// are always safe. The generator still rejects empty lines, as they are most likely a mistake in
+/// the input.
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct Report {
+pub struct Report {
     levels: Vec<i64>,
 }
 
-impl Report {
-    fn sliding_window_check(levels: &[i64]) -> bool {
-        let levels = &levels;
-        let inc = levels[0] < levels[1];
+/// Whether adjacent levels may be equal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Monotonicity {
+    /// Levels have to be strictly increasing or strictly decreasing.
+    #[default]
+    Strict,
+    /// Adjacent levels may also be equal, as long as the delta bounds (or
+    /// [`SafetyPolicy::allow_plateaus`]) permit it. Such plateaus do not decide the direction of
+    /// the report.
+    NonStrict,
+}
+
+/// The rules a report has to follow to be safe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SafetyPolicy {
+    /// Smallest allowed difference between two adjacent levels.
+    pub min_delta: i64,
+    /// Largest allowed difference between two adjacent levels.
+    pub max_delta: i64,
+    pub monotonicity: Monotonicity,
+    /// Whether equal adjacent levels are exempt from the delta bounds. Only has an effect with
+    /// [`Monotonicity::NonStrict`], strict monotonicity never allows them.
+    pub allow_plateaus: bool,
+    /// How many levels the Problem Dampener may remove.
+    pub max_dampened: usize,
+}
+
+impl Default for SafetyPolicy {
+    /// The rules of the puzzle.
+    fn default() -> Self {
+        SafetyPolicy {
+            min_delta: 1,
+            max_delta: 3,
+            monotonicity: Monotonicity::Strict,
+            allow_plateaus: false,
+            max_dampened: 1,
+        }
+    }
+}
 
-        for i in 0..levels.len() - 1 {
-            let [a, b] = &levels[i..(i + 2)] else {
-                unreachable!()
+impl SafetyPolicy {
+    /// Check the step from level `a` to level `b`. `inc` is the direction of the report, which is
+    /// decided by the first step that changes the level.
+    fn check_step(&self, a: i64, b: i64, inc: &mut Option<bool>) -> Result<(), Reason> {
+        if a == b {
+            return match self.monotonicity {
+                Monotonicity::Strict => Err(Reason::ZeroDelta),
+                Monotonicity::NonStrict if self.allow_plateaus || self.min_delta <= 0 => Ok(()),
+                Monotonicity::NonStrict => Err(Reason::DeltaTooSmall),
             };
+        }
+
+        let inc = *inc.get_or_insert(a < b);
+        let diff = (a - b).abs();
+
+        if (a < b) != inc {
+            Err(Reason::DirectionFlip)
+        } else if diff < self.min_delta {
+            Err(Reason::DeltaTooSmall)
+        } else if diff > self.max_delta {
+            Err(Reason::DeltaTooLarge)
+        } else {
+            Ok(())
+        }
+    }
+}
 
-            let inc_dec_correct = if inc { a < b } else { a > b };
+/// Why two adjacent levels make a report unsafe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reason {
+    /// The levels change in the other direction than the report does.
+    DirectionFlip,
+    /// The levels are equal, which strict monotonicity forbids.
+    ZeroDelta,
+    /// The levels differ by less than the minimum delta.
+    DeltaTooSmall,
+    /// The levels differ by more than the maximum delta.
+    DeltaTooLarge,
+}
 
-            let diff = (a - b).abs();
-            let change_is_in_bounds = (1..=3).contains(&diff);
+impl Display for Reason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Reason::DirectionFlip => write!(f, "direction flip"),
+            Reason::ZeroDelta => write!(f, "zero delta"),
+            Reason::DeltaTooSmall => write!(f, "delta too small"),
+            Reason::DeltaTooLarge => write!(f, "delta too large"),
+        }
+    }
+}
+
+/// The first pair of adjacent levels that breaks the rules: `levels[index]` and
+/// `levels[index + 1]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Violation {
+    pub index: usize,
+    pub reason: Reason,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "levels {} and {}: {}",
+            self.index,
+            self.index + 1,
+            self.reason
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Safe,
+    Unsafe(Violation),
+}
+
+impl Verdict {
+    pub fn is_safe(&self) -> bool {
+        matches!(self, Verdict::Safe)
+    }
+}
+
+/// Result of checking a report with the Problem Dampener.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Dampened {
+    /// The report is safe without removing anything.
+    Safe,
+    /// The report is safe after removing the levels at these (ascending) indices.
+    Removed(Vec<usize>),
+    /// The report stays unsafe with as many removals as allowed. Contains the violation of the
+    /// full report.
+    Unsafe(Violation),
+}
+
+impl Dampened {
+    pub fn is_safe(&self) -> bool {
+        !matches!(self, Dampened::Unsafe(_))
+    }
+}
+
+impl Report {
+    pub fn new(levels: Vec<i64>) -> Self {
+        Report { levels }
+    }
 
-            if !inc_dec_correct || !change_is_in_bounds {
-                return false;
+    fn sliding_window_check(
+        levels: impl IntoIterator<Item = i64>,
+        policy: &SafetyPolicy,
+    ) -> Verdict {
+        let mut levels = levels.into_iter();
+        let Some(mut a) = levels.next() else {
+            return Verdict::Safe;
+        };
+        let mut inc = None;
+
+        for (i, b) in levels.enumerate() {
+            if let Err(reason) = policy.check_step(a, b, &mut inc) {
+                return Verdict::Unsafe(Violation { index: i, reason });
             }
+
+            a = b;
         }
 
-        true
+        Verdict::Safe
     }
 
-    fn is_safe(&self) -> bool {
-        Report::sliding_window_check(&self.levels)
+    /// The levels of the report, skipping the one at index `skip`.
+    fn without(&self, skip: usize) -> impl Iterator<Item = i64> + '_ {
+        self.levels
+            .iter()
+            .enumerate()
+            .filter(move |(i, _)| *i != skip)
+            .map(|(_, level)| *level)
     }
 
-    fn is_safe_after_dampening(&self) -> bool {
+    pub fn levels(&self) -> &[i64] {
+        &self.levels
+    }
+
+    /// Check the report against the rules of part 1.
+    pub fn check(&self) -> Verdict {
+        self.check_with(&SafetyPolicy::default())
+    }
+
+    /// Check the report against `policy`, without removing any levels.
+    pub fn check_with(&self, policy: &SafetyPolicy) -> Verdict {
+        Report::sliding_window_check(self.levels.iter().copied(), policy)
+    }
+
+    /// Check the report against the rules of part 2, reporting the first level whose removal
+    /// makes it safe.
+    pub fn check_dampened(&self) -> Dampened {
+        self.check_dampened_with(&SafetyPolicy::default())
+    }
+
+    /// Check the report against `policy`, removing up to `policy.max_dampened` levels.
+    ///
+    /// A single removal takes linear time and reports the first level whose removal makes the
+    /// report safe. More removals take `O(n * max_dampened)` time and report the fewest levels
+    /// to remove.
+    pub fn check_dampened_with(&self, policy: &SafetyPolicy) -> Dampened {
+        let violation = match self.check_with(policy) {
+            Verdict::Safe => return Dampened::Safe,
+            Verdict::Unsafe(violation) => violation,
+        };
+
+        let removed = match policy.max_dampened {
+            0 => None,
+            1 => self.dampen_once(violation, policy).map(|i| vec![i]),
+            k => [true, false]
+                .into_iter()
+                .filter_map(|inc| Report::min_removals(&self.levels, inc, policy, k))
+                .min_by_key(Vec::len)
+                .filter(|removed| removed.len() <= k),
+        };
+
+        removed.map_or(Dampened::Unsafe(violation), Dampened::Removed)
+    }
+
+    /// If the first violation is between `i` and `i + 1`, removing any level after `i + 1` keeps
+    /// that violation. So does removing any level before `i`, unless it is one of the two levels
+    /// of the first step that decides the direction. So at most four candidates have to be
+    /// checked, each in linear time.
+    fn dampen_once(&self, violation: Violation, policy: &SafetyPolicy) -> Option<usize> {
+        let i = violation.index;
+        let first_step = self
+            .levels
+            .windows(2)
+            .position(|pair| pair[0] != pair[1])
+            .unwrap_or_default();
+
+        let mut candidates = [first_step, first_step + 1, i, i + 1];
+        candidates.sort_unstable();
+
+        candidates
+            .into_iter()
+            .find(|&skip| Report::sliding_window_check(self.without(skip), policy).is_safe())
+    }
+
+    /// Reference implementation of [`Report::check_dampened`], which retries the check for every
+    /// possible removal. Quadratic in the number of levels, only kept for tests and benchmarks.
+    #[doc(hidden)]
+    pub fn is_safe_after_dampening_naive(&self) -> bool {
+        if self.check().is_safe() {
+            return true;
+        }
+
         let len = self.levels.len();
         let levels = &self.levels;
         self.levels.iter().enumerate().any(|(i, _)| {
             let new_level = [&levels[0..i], &levels[(i + 1).min(len)..len]].concat();
-            Report::sliding_window_check(&new_level)
+            Report::sliding_window_check(new_level, &SafetyPolicy::default()).is_safe()
+        })
+    }
+
+    /// Whether the report can be made safe under the puzzle rules by removing at most `k` levels.
+    /// Takes `O(n * k)` time.
+    pub fn is_safe_with_removals(&self, k: usize) -> bool {
+        self.check_dampened_with(&SafetyPolicy {
+            max_dampened: k,
+            ..Default::default()
         })
+        .is_safe()
+    }
+
+    /// The fewest levels to remove, so every step of the remaining levels goes in the given
+    /// direction and follows `policy`. At least one level is kept, so this is only `None` for
+    /// empty reports.
+    ///
+    /// Only gaps of up to `k` removed levels between two kept levels are considered, so the
+    /// result is minimal if it has at most `k` levels, and longer than `k` otherwise.
+    fn min_removals(
+        levels: &[i64],
+        inc: bool,
+        policy: &SafetyPolicy,
+        k: usize,
+    ) -> Option<Vec<usize>> {
+        let n = levels.len();
+        // fewest removals within levels[..=i] when keeping levels[i], and the kept level before
+        let mut removals = vec![0; n];
+        let mut previous = vec![None; n];
+        let mut best: Option<(usize, usize)> = None;
+
+        for i in 0..n {
+            // without a kept level before, everything before has to go
+            removals[i] = i;
+
+            for j in i.saturating_sub(k + 1)..i {
+                if policy
+                    .check_step(levels[j], levels[i], &mut Some(inc))
+                    .is_err()
+                {
+                    continue;
+                }
+
+                let count = removals[j] + (i - j - 1);
+                if count < removals[i] {
+                    removals[i] = count;
+                    previous[i] = Some(j);
+                }
+            }
+
+            let total = removals[i] + (n - 1 - i);
+            if best.is_none_or(|(fewest, _)| total < fewest) {
+                best = Some((total, i));
+            }
+        }
+
+        let (_, last) = best?;
+
+        let mut kept = vec![false; n];
+        let mut current = Some(last);
+        while let Some(i) = current {
+            kept[i] = true;
+            current = previous[i];
+        }
+
+        Some((0..n).filter(|i| !kept[*i]).collect())
+    }
+
+    fn is_safe(&self) -> bool {
+        self.check().is_safe()
+    }
+
+    fn is_safe_after_dampening(&self) -> bool {
+        self.check_dampened().is_safe()
     }
 }
 
 #[aoc_generator(day2)]
-fn generator_day2(input: &str) -> Vec<Report> {
-    input
+fn generator_day2(input: &str) -> Result<Vec<Report>, ParseError> {
+    let rows = parse_rows(input, "a level")?;
+
+    if let Some((line, _)) = input
         .lines()
-        .map(|line| Report {
-            levels: line
-                .split_whitespace()
-                .map(|level| level.parse::<i64>().expect("Something went wrong"))
-                .collect(),
-        })
-        .collect()
+        .zip(&rows)
+        .find(|(_, levels)| levels.is_empty())
+    {
+        return Err(ParseError::missing(input, line, "a level"));
+    }
+
+    Ok(rows.into_iter().map(|levels| Report { levels }).collect())
 }
 
 #[aoc(day2, part1)]
@@ -68,10 +375,37 @@ fn part2(reports: &[Report]) -> usize {
         .count()
 }
 
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u32 = 2;
+
+    type Input = Vec<Report>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        generator_day2(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
-    use super::{generator_day2, part1, part2, Report};
+    use crate::utils::{lcg::Lcg, ParseError};
+
+    use super::{
+        generator_day2, part1, part2, Dampened, Monotonicity, Reason, Report, SafetyPolicy,
+        Verdict, Violation,
+    };
 
     const INPUT: &str = "7 6 4 2 1
 1 2 7 8 9
@@ -82,7 +416,7 @@ mod tests {
 
     #[test]
     fn test_generator_day2() {
-        let gen = generator_day2(INPUT);
+        let gen = generator_day2(INPUT).unwrap();
 
         let expected = vec![
             Report {
@@ -110,7 +444,7 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        let gen = generator_day2(INPUT);
+        let gen = generator_day2(INPUT).unwrap();
         let result = part1(&gen);
 
         assert_eq!(result, 2);
@@ -118,9 +452,306 @@ mod tests {
 
     #[test]
     fn test_part2() {
-        let gen = generator_day2(INPUT);
+        let gen = generator_day2(INPUT).unwrap();
         let result = part2(&gen);
 
         assert_eq!(result, 4);
     }
+
+    #[test]
+    fn test_check() {
+        let gen = generator_day2(INPUT).unwrap();
+        let verdicts = gen.iter().map(Report::check).collect::<Vec<_>>();
+
+        assert_eq!(
+            verdicts,
+            vec![
+                Verdict::Safe,
+                Verdict::Unsafe(Violation {
+                    index: 1,
+                    reason: Reason::DeltaTooLarge
+                }),
+                Verdict::Unsafe(Violation {
+                    index: 2,
+                    reason: Reason::DeltaTooLarge
+                }),
+                Verdict::Unsafe(Violation {
+                    index: 1,
+                    reason: Reason::DirectionFlip
+                }),
+                Verdict::Unsafe(Violation {
+                    index: 2,
+                    reason: Reason::ZeroDelta
+                }),
+                Verdict::Safe,
+            ]
+        );
+
+        let Verdict::Unsafe(violation) = verdicts[3] else {
+            unreachable!()
+        };
+        assert_eq!(violation.to_string(), "levels 1 and 2: direction flip");
+    }
+
+    #[test]
+    fn test_check_dampened() {
+        let gen = generator_day2(INPUT).unwrap();
+        let verdicts = gen.iter().map(Report::check_dampened).collect::<Vec<_>>();
+
+        assert_eq!(
+            verdicts,
+            vec![
+                Dampened::Safe,
+                Dampened::Unsafe(Violation {
+                    index: 1,
+                    reason: Reason::DeltaTooLarge
+                }),
+                Dampened::Unsafe(Violation {
+                    index: 2,
+                    reason: Reason::DeltaTooLarge
+                }),
+                Dampened::Removed(vec![1]),
+                Dampened::Removed(vec![2]),
+                Dampened::Safe,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dampening_matches_naive() {
+        // small values make all kinds of violations likely
+        let mut rng = Lcg::new(7);
+
+        for _ in 0..5000 {
+            let len = rng.below(8) as usize + 1;
+            let report = Report::new((0..len).map(|_| rng.below(10) as i64).collect());
+
+            let naive = report.is_safe_after_dampening_naive();
+            assert_eq!(report.check_dampened().is_safe(), naive, "{report:?}");
+            assert_eq!(report.is_safe_with_removals(1), naive, "{report:?}");
+            assert_eq!(
+                report.is_safe_with_removals(0),
+                report.check().is_safe(),
+                "{report:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_removals() {
+        let report = Report::new(vec![1, 9, 2, 9, 3, 4]);
+
+        assert!(!report.is_safe_with_removals(1));
+        assert!(report.is_safe_with_removals(2));
+
+        // everything but a single level can always be removed
+        let report = Report::new(vec![5, 5, 5, 5]);
+        assert!(!report.is_safe_with_removals(2));
+        assert!(report.is_safe_with_removals(3));
+    }
+
+    #[test]
+    fn test_policy() {
+        let report = Report::new(vec![1, 1, 2, 6, 7]);
+
+        assert_eq!(
+            report.check(),
+            Verdict::Unsafe(Violation {
+                index: 0,
+                reason: Reason::ZeroDelta
+            })
+        );
+
+        let plateaus = SafetyPolicy {
+            monotonicity: Monotonicity::NonStrict,
+            allow_plateaus: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            report.check_with(&plateaus),
+            Verdict::Unsafe(Violation {
+                index: 2,
+                reason: Reason::DeltaTooLarge
+            })
+        );
+
+        let wide = SafetyPolicy {
+            min_delta: 2,
+            max_delta: 4,
+            ..plateaus
+        };
+        let report = Report::new(vec![1, 1, 2, 4, 5, 7]);
+        assert_eq!(report.check_with(&plateaus), Verdict::Safe);
+        assert_eq!(
+            report.check_dampened_with(&wide),
+            Dampened::Unsafe(Violation {
+                index: 1,
+                reason: Reason::DeltaTooSmall
+            })
+        );
+
+        // either 2 and 3 or 2 and 4 can go
+        let Dampened::Removed(removed) = report.check_dampened_with(&SafetyPolicy {
+            max_dampened: 2,
+            ..wide
+        }) else {
+            panic!("two removals should be enough");
+        };
+        assert_eq!(removed.len(), 2);
+        assert_eq!(removed[0], 2);
+
+        let undampened = SafetyPolicy {
+            max_dampened: 0,
+            ..Default::default()
+        };
+        assert!(!Report::new(vec![1, 2, 2])
+            .check_dampened_with(&undampened)
+            .is_safe());
+    }
+
+    #[test]
+    fn test_plateaus() {
+        let report = Report::new(vec![1, 2, 2, 4]);
+        let check = |monotonicity, allow_plateaus, min_delta| {
+            report.check_with(&SafetyPolicy {
+                min_delta,
+                monotonicity,
+                allow_plateaus,
+                ..Default::default()
+            })
+        };
+        let unsafe_at = |reason| Verdict::Unsafe(Violation { index: 1, reason });
+
+        assert_eq!(
+            check(Monotonicity::Strict, false, 1),
+            unsafe_at(Reason::ZeroDelta)
+        );
+        assert_eq!(
+            check(Monotonicity::Strict, true, 1),
+            unsafe_at(Reason::ZeroDelta)
+        );
+        assert_eq!(
+            check(Monotonicity::NonStrict, false, 1),
+            unsafe_at(Reason::DeltaTooSmall)
+        );
+        assert_eq!(check(Monotonicity::NonStrict, false, 0), Verdict::Safe);
+        assert_eq!(check(Monotonicity::NonStrict, true, 1), Verdict::Safe);
+
+        // plateaus are only exempt from the delta bounds, not from the direction
+        assert_eq!(
+            Report::new(vec![3, 3, 4, 2]).check_with(&SafetyPolicy {
+                monotonicity: Monotonicity::NonStrict,
+                allow_plateaus: true,
+                ..Default::default()
+            }),
+            Verdict::Unsafe(Violation {
+                index: 2,
+                reason: Reason::DirectionFlip
+            })
+        );
+    }
+
+    #[test]
+    fn test_dampening_matches_brute_force() {
+        let mut rng = Lcg::new(11);
+
+        let safe_without = |report: &Report, policy: &SafetyPolicy, removed: &[usize]| {
+            let levels = report
+                .levels()
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| !removed.contains(i))
+                .map(|(_, level)| *level);
+            Report::sliding_window_check(levels, policy).is_safe()
+        };
+
+        for _ in 0..3000 {
+            let len = rng.below(7) as usize + 1;
+            let report = Report::new((0..len).map(|_| rng.below(8) as i64).collect());
+            let min_delta = rng.below(3) as i64;
+            let policy = SafetyPolicy {
+                min_delta,
+                max_delta: min_delta + rng.below(3) as i64,
+                monotonicity: *rng.pick(&[Monotonicity::Strict, Monotonicity::NonStrict]),
+                allow_plateaus: *rng.pick(&[false, true]),
+                max_dampened: rng.below(3) as usize,
+            };
+
+            let mut removals = vec![vec![]];
+            for i in 0..len {
+                removals.push(vec![i]);
+                for j in i + 1..len {
+                    removals.push(vec![i, j]);
+                }
+            }
+            removals.sort_by_key(Vec::len);
+            let expected = removals
+                .into_iter()
+                .filter(|removed| removed.len() <= policy.max_dampened)
+                .find(|removed| safe_without(&report, &policy, removed));
+
+            let dampened = report.check_dampened_with(&policy);
+            assert_eq!(
+                dampened.is_safe(),
+                expected.is_some(),
+                "{report:?} {policy:?}"
+            );
+
+            match (dampened, expected) {
+                (Dampened::Removed(removed), Some(expected)) => {
+                    assert!(safe_without(&report, &policy, &removed));
+                    assert_eq!(removed.len(), expected.len(), "{report:?} {policy:?}");
+                }
+                (Dampened::Safe, Some(expected)) => assert!(expected.is_empty()),
+                _ => {}
+            }
+        }
+    }
+
+    #[test]
+    fn test_short_reports() {
+        for levels in [vec![], vec![5]] {
+            let report = Report::new(levels);
+
+            assert_eq!(report.check(), Verdict::Safe);
+            assert_eq!(report.check_dampened(), Dampened::Safe);
+            assert!(report.is_safe_after_dampening_naive());
+            assert!(report.is_safe_with_removals(0));
+        }
+
+        let report = Report::new(vec![5, 5]);
+        assert_eq!(
+            report.check(),
+            Verdict::Unsafe(Violation {
+                index: 0,
+                reason: Reason::ZeroDelta
+            })
+        );
+        assert_eq!(report.check_dampened(), Dampened::Removed(vec![0]));
+        assert!(report.is_safe_with_removals(1));
+    }
+
+    #[test]
+    fn test_generator_short_reports() {
+        let gen = generator_day2("1 2\n4\n3 2").unwrap();
+        assert_eq!(part1(&gen), 3);
+
+        assert_eq!(generator_day2(""), Ok(vec![]));
+        assert_eq!(
+            generator_day2("1 2\n\n3 2"),
+            Err(ParseError::MissingToken {
+                line: 2,
+                column: 1,
+                expected: "a level"
+            })
+        );
+        assert_eq!(
+            generator_day2("1 2\n3 2\n  "),
+            Err(ParseError::MissingToken {
+                line: 3,
+                column: 3,
+                expected: "a level"
+            })
+        );
+    }
 }
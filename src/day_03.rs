@@ -1,8 +1,17 @@
+use std::ops::Range;
+
 use aoc_runner_derive::{aoc, aoc_generator};
-use regex::{Captures, Regex};
+
+use crate::{solution::Solution, utils::ParseError};
+
+use self::vm::{Call, InstructionSet, Machine};
+
+pub mod stream;
+pub mod trace;
+pub mod vm;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Mul(i64, i64);
+pub struct Mul(i64, i64);
 
 impl Mul {
     fn calc(&self) -> i64 {
@@ -10,66 +19,183 @@ impl Mul {
     }
 }
 
-#[aoc_generator(day3, part1)]
-fn generator_part1(input: &str) -> Vec<Mul> {
-    let re = Regex::new(r"(?m)mul\(([0-9]+),*([0-9]+)\)").unwrap();
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Mul(Mul),
+    Do,
+    Dont,
+}
 
-    re.captures_iter(input)
-        .map(|c| c.extract())
-        .map(|(_, [left, right])| Mul(left.parse().unwrap(), right.parse().unwrap()))
-        .collect()
+/// An instruction together with the bytes of the corrupted memory it was parsed from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Located<T = Instruction> {
+    pub instruction: T,
+    pub span: Range<usize>,
 }
 
-#[aoc(day3, part1)]
-fn part1(muls: &[Mul]) -> i64 {
-    muls.iter().map(Mul::calc).sum()
+/// Scans corrupted memory for instructions, skipping everything else.
+///
+/// `parse` recognizes an instruction at the very start of the remaining bytes and returns it with
+/// its length. The puzzle grammar used by [`scan`] is exact: `mul(X,Y)` with operands of 1 to 3
+/// digits separated by a single comma, `do()` and `don't()`. Anything else, e.g. `mul(12)`,
+/// `mul(1,,2)` or `mul( 1,2)`, is corruption.
+pub struct Scanner<'a, F = fn(&[u8]) -> Option<(Instruction, usize)>> {
+    input: &'a [u8],
+    position: usize,
+    parse: F,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-enum Instruction {
-    Mul(Mul),
-    Do,
-    Dont,
+impl<'a, F> Scanner<'a, F> {
+    pub fn new(input: &'a str, parse: F) -> Self {
+        Scanner {
+            input: input.as_bytes(),
+            position: 0,
+            parse,
+        }
+    }
 }
 
-impl<'h> From<Captures<'h>> for Instruction {
-    fn from(cap: Captures<'h>) -> Self {
-        match &cap[0] {
-            "do()" => Instruction::Do,
-            "don't()" => Instruction::Dont,
-            mul if mul.starts_with("mul(") => {
-                Instruction::Mul(Mul(cap[2].parse().unwrap(), cap[3].parse().unwrap()))
+pub fn scan(input: &str) -> Scanner<'_> {
+    Scanner::new(input, instruction_at)
+}
+
+impl<T, F: FnMut(&[u8]) -> Option<(T, usize)>> Iterator for Scanner<'_, F> {
+    type Item = Located<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.position < self.input.len() {
+            let start = self.position;
+
+            if let Some((instruction, len)) = (self.parse)(&self.input[start..]) {
+                self.position += len;
+                return Some(Located {
+                    instruction,
+                    span: start..start + len,
+                });
             }
-            _ => unreachable!(),
+
+            self.position += 1;
         }
+
+        None
     }
 }
 
+/// Parse the instruction at the very start of `bytes`, returning it and its length.
+fn instruction_at(bytes: &[u8]) -> Option<(Instruction, usize)> {
+    let mut operands = [0; 2];
+    if let Some(len) = call_at(bytes, "mul", &mut operands) {
+        return Some((Instruction::Mul(Mul(operands[0], operands[1])), len));
+    }
+
+    if let Some(len) = call_at(bytes, "do", &mut []) {
+        return Some((Instruction::Do, len));
+    }
+
+    call_at(bytes, "don't", &mut []).map(|len| (Instruction::Dont, len))
+}
+
+/// Parse a call `name(a,b,...)` at the very start of `bytes`, with exactly `operands.len()`
+/// operands separated by single commas. Fills `operands` and returns the length of the call.
+fn call_at(bytes: &[u8], name: &str, operands: &mut [i64]) -> Option<usize> {
+    let mut rest = bytes.strip_prefix(name.as_bytes())?.strip_prefix(b"(")?;
+
+    for (i, slot) in operands.iter_mut().enumerate() {
+        if i > 0 {
+            rest = rest.strip_prefix(b",")?;
+        }
+
+        let (value, after) = operand(rest)?;
+        *slot = value;
+        rest = after;
+    }
+
+    let rest = rest.strip_prefix(b")")?;
+
+    Some(bytes.len() - rest.len())
+}
+
+/// Parse an operand of 1 to 3 digits at the start of `bytes`.
+fn operand(bytes: &[u8]) -> Option<(i64, &[u8])> {
+    let digits = bytes
+        .iter()
+        .take(3)
+        .take_while(|b| b.is_ascii_digit())
+        .count();
+
+    if digits == 0 {
+        return None;
+    }
+
+    let value = bytes[..digits]
+        .iter()
+        .fold(0, |value, digit| value * 10 + i64::from(digit - b'0'));
+
+    Some((value, &bytes[digits..]))
+}
+
+#[aoc_generator(day3, part1)]
+fn generator_part1(input: &str) -> Vec<Mul> {
+    muls(&generator_part2(input))
+}
+
+#[aoc(day3, part1)]
+fn part1(muls: &[Mul]) -> i64 {
+    muls.iter().map(Mul::calc).sum()
+}
+
 #[aoc_generator(day3, part2)]
 fn generator_part2(input: &str) -> Vec<Instruction> {
-    let re = Regex::new(r"(?m)(mul\(([0-9]+),*([0-9]+)\)|(don't\(\))|(do\(\)))").unwrap();
-    re.captures_iter(input).map(Instruction::from).collect()
+    scan(input).map(|located| located.instruction).collect()
 }
 
-#[aoc(day3, part2)]
-fn part2(instructions: &[Instruction]) -> i64 {
-    let mut sum = 0;
-    let mut cando = true;
+/// All multiplications of a program, regardless of whether they are enabled.
+fn muls(instructions: &[Instruction]) -> Vec<Mul> {
     instructions
         .iter()
-        .for_each(|instruction| match instruction {
-            Instruction::Mul(mul) if cando => sum += mul.calc(),
-            Instruction::Do => cando = true,
-            Instruction::Dont => cando = false,
-            _ => {}
-        });
+        .filter_map(|instruction| match instruction {
+            Instruction::Mul(mul) => Some(*mul),
+            _ => None,
+        })
+        .collect()
+}
+
+#[aoc(day3, part2)]
+fn part2(instructions: &[Instruction]) -> i64 {
+    let mut machine = Machine::new(InstructionSet::puzzle());
+
+    for instruction in instructions {
+        machine.execute(&Call::from(*instruction));
+    }
 
-    sum
+    machine.state().accumulator
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u32 = 3;
+
+    type Input = Vec<Instruction>;
+    type Answer1 = i64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        Ok(generator_part2(input))
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(&muls(input))
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::day_03::{generator_part2, Instruction, Mul};
+    use crate::day_03::{generator_part2, scan, Instruction, Located, Mul};
 
     use super::{generator_part1, part1, part2};
 
@@ -93,7 +219,7 @@ mod tests {
         assert_eq!(result, 161);
     }
 
-    const INPUT2: &str =
+    pub(super) const INPUT2: &str =
         "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
 
     #[test]
@@ -118,4 +244,42 @@ mod tests {
 
         assert_eq!(result, 48);
     }
+
+    #[test]
+    fn test_scan_spans() {
+        let spans = scan(INPUT2)
+            .map(|Located { span, .. }| span)
+            .collect::<Vec<_>>();
+
+        assert_eq!(spans, vec![1..9, 20..27, 28..36, 48..57, 59..63, 64..72]);
+        assert_eq!(&INPUT2[20..27], "don't()");
+    }
+
+    #[test]
+    fn test_scan_edge_cases() {
+        assert_eq!(generator_part1("mul(12)"), vec![]);
+        assert_eq!(generator_part1("mul(1,,,2)"), vec![]);
+        assert_eq!(generator_part1("mul(1,2,3)"), vec![]);
+        assert_eq!(generator_part1("mul(1234,5)mul(5,1234)"), vec![]);
+        assert_eq!(generator_part1("mul( 1,2)mul(1 ,2)mul(1,2 )"), vec![]);
+        assert_eq!(generator_part1("mul(-1,2)mul(1,+2)"), vec![]);
+        assert_eq!(generator_part1("MUL(1,2)mul[1,2]mul(1,2"), vec![]);
+        assert_eq!(
+            generator_part1("mul(123,4)mul(0,007)"),
+            vec![Mul(123, 4), Mul(0, 7)]
+        );
+
+        // a broken instruction does not hide the one right after it
+        assert_eq!(
+            scan("mul(mul(2,3)").collect::<Vec<_>>(),
+            vec![Located {
+                instruction: Instruction::Mul(Mul(2, 3)),
+                span: 4..12
+            }]
+        );
+        assert_eq!(
+            generator_part2("do(don't()do()don't(x)"),
+            vec![Instruction::Dont, Instruction::Do]
+        );
+    }
 }
@@ -0,0 +1,184 @@
+use std::io::{self, BufRead};
+
+use super::{
+    instruction_at,
+    vm::{Call, InstructionSet, Machine},
+    Located,
+};
+
+/// Length of the longest instruction, `mul(123,123)`. Whether an instruction starts at some
+/// position can always be decided by looking at this many bytes.
+const MAX_INSTRUCTION_LEN: usize = 12;
+
+/// An instruction found in the stream, and the sum of part 2 after executing it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Progress {
+    pub instruction: Located,
+    pub sum: i64,
+}
+
+/// Scans corrupted memory from a reader in chunks of `chunk_size` bytes, executing the
+/// instructions with the semantics of part 2 as they are found.
+///
+/// Besides the current chunk, only the last few bytes of the previous one are kept, in case an
+/// instruction is split between them. Spans are byte offsets from the start of the stream.
+pub struct Stream<R> {
+    reader: R,
+    chunk_size: usize,
+    buffer: Vec<u8>,
+    /// Offset of `buffer[0]` in the stream.
+    start: usize,
+    /// Index into `buffer` of the next byte to scan.
+    position: usize,
+    eof: bool,
+    machine: Machine,
+}
+
+pub fn stream<R: BufRead>(reader: R, chunk_size: usize) -> Stream<R> {
+    assert!(chunk_size > 0, "chunks have to hold at least one byte");
+
+    Stream {
+        reader,
+        chunk_size,
+        buffer: Vec::with_capacity(chunk_size + MAX_INSTRUCTION_LEN),
+        start: 0,
+        position: 0,
+        eof: false,
+        machine: Machine::new(InstructionSet::puzzle()),
+    }
+}
+
+/// The result of part 2 for all of `reader`.
+pub fn stream_part2(reader: impl BufRead, chunk_size: usize) -> io::Result<i64> {
+    let mut sum = 0;
+
+    for progress in stream(reader, chunk_size) {
+        sum = progress?.sum;
+    }
+
+    Ok(sum)
+}
+
+impl<R: BufRead> Stream<R> {
+    /// Drop everything that has been scanned already and append the next chunk to the buffer.
+    fn read_chunk(&mut self) -> io::Result<()> {
+        self.buffer.drain(..self.position);
+        self.start += self.position;
+        self.position = 0;
+
+        let available = self.reader.fill_buf()?;
+        let len = available.len().min(self.chunk_size);
+        self.buffer.extend_from_slice(&available[..len]);
+        self.reader.consume(len);
+
+        self.eof = len == 0;
+
+        Ok(())
+    }
+}
+
+impl<R: BufRead> Iterator for Stream<R> {
+    type Item = io::Result<Progress>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            // positions near the end of the buffer may start an instruction that continues in
+            // the next chunk
+            let limit = if self.eof {
+                self.buffer.len()
+            } else {
+                self.buffer.len().saturating_sub(MAX_INSTRUCTION_LEN - 1)
+            };
+
+            while self.position < limit {
+                let start = self.position;
+
+                if let Some((instruction, len)) = instruction_at(&self.buffer[start..]) {
+                    self.position += len;
+                    self.machine.execute(&Call::from(instruction));
+
+                    let offset = self.start + start;
+                    return Some(Ok(Progress {
+                        instruction: Located {
+                            instruction,
+                            span: offset..offset + len,
+                        },
+                        sum: self.machine.state().accumulator,
+                    }));
+                }
+
+                self.position += 1;
+            }
+
+            if self.eof {
+                return None;
+            }
+
+            if let Err(e) = self.read_chunk() {
+                return Some(Err(e));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        day_03::{generator_part2, part2, scan, tests::INPUT2},
+        utils::lcg::Lcg,
+    };
+
+    use super::{stream, stream_part2, Progress};
+
+    #[test]
+    fn test_stream_matches_scan() {
+        let expected = scan(INPUT2).collect::<Vec<_>>();
+
+        for chunk_size in 1..=INPUT2.len() + 1 {
+            let progress = stream(INPUT2.as_bytes(), chunk_size)
+                .collect::<Result<Vec<_>, _>>()
+                .unwrap();
+
+            let found = progress
+                .iter()
+                .map(|Progress { instruction, .. }| instruction.clone())
+                .collect::<Vec<_>>();
+            assert_eq!(found, expected, "chunk size {chunk_size}");
+
+            let sums = progress.iter().map(|p| p.sum).collect::<Vec<_>>();
+            assert_eq!(sums, vec![8, 8, 8, 8, 8, 48], "chunk size {chunk_size}");
+        }
+    }
+
+    #[test]
+    fn test_stream_part2() {
+        // a simple LCG picks pieces of (broken) instructions, so many of them are split between
+        // chunks
+        let pieces = [
+            "mul(",
+            "12",
+            "3",
+            ",",
+            ")",
+            "do()",
+            "don't()",
+            "mul(7,8)",
+            "x",
+            "mul(123,456)",
+        ];
+        let mut rng = Lcg::new(3);
+        let input = (0..5000).map(|_| *rng.pick(&pieces)).collect::<String>();
+
+        let expected = part2(&generator_part2(&input));
+
+        for chunk_size in [1, 2, 3, 7, 11, 12, 13, 64, 4096, 1 << 20] {
+            assert_eq!(
+                stream_part2(input.as_bytes(), chunk_size).unwrap(),
+                expected,
+                "chunk size {chunk_size}"
+            );
+        }
+
+        assert_eq!(stream_part2("".as_bytes(), 4).unwrap(), 0);
+    }
+}
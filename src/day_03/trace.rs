@@ -0,0 +1,217 @@
+use std::ops::Range;
+
+use crate::utils::ansi::{paint, Color};
+
+use super::{
+    scan,
+    vm::{Call, InstructionSet, Machine},
+    Instruction, Located,
+};
+
+/// One instruction of a [`Trace`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub instruction: Located,
+    /// Whether the instruction took effect. Only multiplications can be skipped, when a `don't()`
+    /// is in effect.
+    pub applied: bool,
+    /// Span of the `do()` or `don't()` in effect before this instruction, `None` if there was
+    /// none yet and multiplications are enabled.
+    pub condition: Option<Range<usize>>,
+    /// Sum of all multiplications up to here, as in part 1.
+    pub total: i64,
+    /// Sum of all applied multiplications up to here, as in part 2.
+    pub enabled_total: i64,
+}
+
+/// Every instruction of some corrupted memory, annotated with how part 2 executed it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trace<'a> {
+    input: &'a str,
+    steps: Vec<Step>,
+}
+
+pub fn trace(input: &str) -> Trace<'_> {
+    let mut machine = Machine::new(InstructionSet::puzzle());
+    let mut condition = None;
+    let mut total = 0;
+
+    let steps = scan(input)
+        .map(|located| {
+            let applied = match located.instruction {
+                Instruction::Mul(mul) => {
+                    total += mul.calc();
+                    machine.state().enabled
+                }
+                Instruction::Do | Instruction::Dont => true,
+            };
+            machine.execute(&Call::from(located.instruction));
+
+            let is_condition = !matches!(located.instruction, Instruction::Mul(_));
+            let step = Step {
+                applied,
+                condition: condition.clone(),
+                total,
+                enabled_total: machine.state().accumulator,
+                instruction: located,
+            };
+
+            if is_condition {
+                condition = Some(step.instruction.span.clone());
+            }
+
+            step
+        })
+        .collect();
+
+    Trace { input, steps }
+}
+
+impl Trace<'_> {
+    pub fn steps(&self) -> &[Step] {
+        &self.steps
+    }
+
+    /// The result of part 1.
+    pub fn total(&self) -> i64 {
+        self.steps.last().map_or(0, |step| step.total)
+    }
+
+    /// The result of part 2.
+    pub fn enabled_total(&self) -> i64 {
+        self.steps.last().map_or(0, |step| step.enabled_total)
+    }
+
+    /// Export the trace as a JSON object.
+    ///
+    /// Instructions only consist of ASCII letters, digits, `'`, `(`, `,` and `)`, so their text
+    /// never has to be escaped.
+    pub fn to_json(&self) -> String {
+        let span = |span: &Range<usize>| {
+            format!(
+                r#"{{"text": "{}", "start": {}, "end": {}}}"#,
+                &self.input[span.clone()],
+                span.start,
+                span.end
+            )
+        };
+
+        let steps = self
+            .steps
+            .iter()
+            .map(|step| {
+                format!(
+                    r#"    {{"instruction": {}, "applied": {}, "condition": {}, "total": {}, "enabled_total": {}}}"#,
+                    span(&step.instruction.span),
+                    step.applied,
+                    step.condition.as_ref().map_or("null".to_string(), span),
+                    step.total,
+                    step.enabled_total
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(",\n");
+
+        format!(
+            "{{\n  \"steps\": [\n{steps}\n  ],\n  \"total\": {},\n  \"enabled_total\": {}\n}}",
+            self.total(),
+            self.enabled_total()
+        )
+    }
+
+    /// Render the corrupted memory for a terminal: applied multiplications are green, skipped
+    /// ones red, `do()` cyan, `don't()` yellow and everything else dimmed. The totals follow on a
+    /// separate line.
+    pub fn to_terminal(&self) -> String {
+        let mut output = String::new();
+        let mut position = 0;
+
+        for step in &self.steps {
+            let Range { start, end } = step.instruction.span;
+
+            if position < start {
+                output += &paint(&self.input[position..start], Color::DIM);
+            }
+
+            let color = match step.instruction.instruction {
+                Instruction::Mul(_) if step.applied => Color::GREEN,
+                Instruction::Mul(_) => Color::RED,
+                Instruction::Do => Color::CYAN,
+                Instruction::Dont => Color::YELLOW,
+            };
+            output += &paint(&self.input[start..end], color);
+
+            position = end;
+        }
+
+        if position < self.input.len() {
+            output += &paint(&self.input[position..], Color::DIM);
+        }
+
+        output += &format!(
+            "\n\ntotal: {}, enabled total: {}",
+            self.total(),
+            self.enabled_total()
+        );
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_03::tests::INPUT2;
+
+    use super::trace;
+
+    #[test]
+    fn test_trace() {
+        let trace = trace(INPUT2);
+        let steps = trace.steps();
+
+        assert_eq!(steps.len(), 6);
+        assert_eq!(trace.total(), 161);
+        assert_eq!(trace.enabled_total(), 48);
+
+        assert!(steps[0].applied);
+        assert_eq!(steps[0].condition, None);
+
+        // mul(5,5) is skipped because of the don't() before it
+        assert!(!steps[2].applied);
+        assert_eq!(steps[2].condition, Some(20..27));
+        assert_eq!((steps[2].total, steps[2].enabled_total), (33, 8));
+
+        // the don't() is still in effect right before the do()
+        assert!(steps[4].applied);
+        assert_eq!(steps[4].condition, Some(20..27));
+        assert_eq!(steps[5].condition, Some(59..63));
+    }
+
+    #[test]
+    fn test_to_json() {
+        let json = trace("mul(2,3)don't()mul(1,1)").to_json();
+
+        assert_eq!(
+            json,
+            r#"{
+  "steps": [
+    {"instruction": {"text": "mul(2,3)", "start": 0, "end": 8}, "applied": true, "condition": null, "total": 6, "enabled_total": 6},
+    {"instruction": {"text": "don't()", "start": 8, "end": 15}, "applied": true, "condition": null, "total": 6, "enabled_total": 6},
+    {"instruction": {"text": "mul(1,1)", "start": 15, "end": 23}, "applied": false, "condition": {"text": "don't()", "start": 8, "end": 15}, "total": 7, "enabled_total": 6}
+  ],
+  "total": 7,
+  "enabled_total": 6
+}"#
+        );
+    }
+
+    #[test]
+    fn test_to_terminal() {
+        let output = trace("xmul(2,3)don't()mul(1,1)?").to_terminal();
+
+        assert_eq!(
+            output,
+            "\x1b[2mx\x1b[0m\x1b[32mmul(2,3)\x1b[0m\x1b[33mdon't()\x1b[0m\x1b[31mmul(1,1)\x1b[0m\x1b[2m?\x1b[0m\n\ntotal: 7, enabled total: 6"
+        );
+    }
+}
@@ -0,0 +1,310 @@
+use std::{collections::HashMap, ops::Range};
+
+use super::{call_at, Instruction, Located, Scanner};
+
+/// State of a [`Machine`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct State {
+    pub enabled: bool,
+    pub accumulator: i64,
+}
+
+impl Default for State {
+    fn default() -> Self {
+        State {
+            enabled: true,
+            accumulator: 0,
+        }
+    }
+}
+
+/// An instruction the machine understands.
+///
+/// It is written as `name(a,b,...)` with exactly `arity` operands of 1 to 3 digits, separated by
+/// single commas. `execute` applies it to the state of the machine.
+#[derive(Debug, Clone, Copy)]
+pub struct Definition {
+    pub name: &'static str,
+    pub arity: usize,
+    pub execute: fn(&mut State, &[i64]),
+}
+
+/// A parsed instruction, referring to a [`Definition`] by name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Call {
+    pub name: &'static str,
+    pub operands: Vec<i64>,
+}
+
+impl From<Instruction> for Call {
+    fn from(instruction: Instruction) -> Self {
+        let (name, operands) = match instruction {
+            Instruction::Mul(mul) => ("mul", vec![mul.0, mul.1]),
+            Instruction::Do => ("do", vec![]),
+            Instruction::Dont => ("don't", vec![]),
+        };
+
+        Call { name, operands }
+    }
+}
+
+/// The instructions a [`Machine`] knows, keyed by name.
+#[derive(Debug, Clone, Default)]
+pub struct InstructionSet {
+    definitions: HashMap<&'static str, Definition>,
+}
+
+impl InstructionSet {
+    /// Add `definition`, replacing any instruction of the same name.
+    pub fn register(&mut self, definition: Definition) {
+        self.definitions.insert(definition.name, definition);
+    }
+
+    /// `mul(a,b)`, `do()` and `don't()` with the semantics of part 2.
+    pub fn puzzle() -> Self {
+        let mut set = InstructionSet::default();
+
+        set.register(Definition {
+            name: "mul",
+            arity: 2,
+            execute: |state, operands| {
+                if state.enabled {
+                    state.accumulator += operands[0] * operands[1];
+                }
+            },
+        });
+        set.register(Definition {
+            name: "do",
+            arity: 0,
+            execute: |state, _| state.enabled = true,
+        });
+        set.register(Definition {
+            name: "don't",
+            arity: 0,
+            execute: |state, _| state.enabled = false,
+        });
+
+        set
+    }
+
+    /// The puzzle instructions plus `add(a,b)`, `sub(a,b)`, `toggle()` and `reset()`.
+    pub fn extended() -> Self {
+        let mut set = InstructionSet::puzzle();
+
+        set.register(Definition {
+            name: "add",
+            arity: 2,
+            execute: |state, operands| {
+                if state.enabled {
+                    state.accumulator += operands[0] + operands[1];
+                }
+            },
+        });
+        set.register(Definition {
+            name: "sub",
+            arity: 2,
+            execute: |state, operands| {
+                if state.enabled {
+                    state.accumulator += operands[0] - operands[1];
+                }
+            },
+        });
+        set.register(Definition {
+            name: "toggle",
+            arity: 0,
+            execute: |state, _| state.enabled = !state.enabled,
+        });
+        set.register(Definition {
+            name: "reset",
+            arity: 0,
+            execute: |state, _| state.accumulator = 0,
+        });
+
+        set
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Definition> {
+        self.definitions.get(name)
+    }
+
+    /// Find all known instructions in corrupted memory, together with their byte spans.
+    pub fn scan<'a>(&'a self, input: &'a str) -> impl Iterator<Item = (Call, Range<usize>)> + 'a {
+        Scanner::new(input, |bytes: &[u8]| self.call_at(bytes))
+            .map(|Located { instruction, span }| (instruction, span))
+    }
+
+    /// Parse a call of any known instruction at the very start of `bytes`, returning it and its
+    /// length.
+    fn call_at(&self, bytes: &[u8]) -> Option<(Call, usize)> {
+        self.definitions
+            .values()
+            .filter(|definition| bytes.starts_with(definition.name.as_bytes()))
+            .find_map(|definition| {
+                let mut operands = vec![0; definition.arity];
+                let len = call_at(bytes, definition.name, &mut operands)?;
+                let call = Call {
+                    name: definition.name,
+                    operands,
+                };
+
+                Some((call, len))
+            })
+    }
+}
+
+/// A tiny interpreter executing calls against an [`InstructionSet`].
+#[derive(Debug, Clone)]
+pub struct Machine {
+    set: InstructionSet,
+    state: State,
+}
+
+impl Machine {
+    pub fn new(set: InstructionSet) -> Self {
+        Machine {
+            set,
+            state: State::default(),
+        }
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    /// Execute a single call. Calls of unknown instructions or with the wrong number of operands
+    /// are ignored, just like corrupted memory.
+    pub fn execute(&mut self, call: &Call) {
+        execute(&self.set, &mut self.state, call);
+    }
+
+    /// Scan `input` for known instructions and execute them, returning the accumulator.
+    pub fn run(&mut self, input: &str) -> i64 {
+        for (call, _) in self.set.scan(input) {
+            execute(&self.set, &mut self.state, &call);
+        }
+
+        self.state.accumulator
+    }
+}
+
+fn execute(set: &InstructionSet, state: &mut State, call: &Call) {
+    if let Some(definition) = set.get(call.name) {
+        if definition.arity == call.operands.len() {
+            (definition.execute)(state, &call.operands);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::day_03::{scan, tests::INPUT2};
+
+    use super::{Call, Definition, InstructionSet, Machine, State};
+
+    #[test]
+    fn test_puzzle_preset() {
+        let mut machine = Machine::new(InstructionSet::puzzle());
+
+        assert_eq!(machine.run(INPUT2), 48);
+        assert_eq!(
+            machine.state(),
+            State {
+                enabled: true,
+                accumulator: 48
+            }
+        );
+    }
+
+    #[test]
+    fn test_puzzle_preset_matches_scanner() {
+        let set = InstructionSet::puzzle();
+        let input = "mul(1,2)do()don't()mul(1234,5)mul(12,345)do(1)don't()";
+
+        assert_eq!(
+            set.scan(input).collect::<Vec<_>>(),
+            scan(input)
+                .map(|located| (Call::from(located.instruction), located.span))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_scan() {
+        let set = InstructionSet::extended();
+        let calls = set
+            .scan("add(1,2)sub(3)toggle()mul(4,5)reset(1)reset()")
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            calls,
+            vec![
+                (
+                    Call {
+                        name: "add",
+                        operands: vec![1, 2]
+                    },
+                    0..8
+                ),
+                (
+                    Call {
+                        name: "toggle",
+                        operands: vec![]
+                    },
+                    14..22
+                ),
+                (
+                    Call {
+                        name: "mul",
+                        operands: vec![4, 5]
+                    },
+                    22..30
+                ),
+                (
+                    Call {
+                        name: "reset",
+                        operands: vec![]
+                    },
+                    38..45
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extended() {
+        let mut machine = Machine::new(InstructionSet::extended());
+
+        assert_eq!(machine.run("add(1,2)sub(3,5)mul(2,3)"), 7);
+        assert_eq!(machine.run("toggle()add(100,100)toggle()sub(1,0)"), 8);
+        assert_eq!(machine.run("reset()mul(3,3)"), 9);
+
+        // the puzzle preset does not know the extra instructions
+        assert_eq!(
+            Machine::new(InstructionSet::puzzle()).run("add(1,2)mul(3,3)"),
+            9
+        );
+    }
+
+    #[test]
+    fn test_register() {
+        let mut set = InstructionSet::puzzle();
+        set.register(Definition {
+            name: "sq",
+            arity: 1,
+            execute: |state, operands| state.accumulator += operands[0] * operands[0],
+        });
+        let mut machine = Machine::new(set);
+
+        assert_eq!(machine.run("sq(3)sq(3,4)mul(2,2)"), 13);
+
+        machine.execute(&Call {
+            name: "sq",
+            operands: vec![],
+        });
+        machine.execute(&Call {
+            name: "unknown",
+            operands: vec![1],
+        });
+        assert_eq!(machine.state().accumulator, 13);
+    }
+}
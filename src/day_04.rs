@@ -1,136 +1,72 @@
-use std::ops::{Add, Mul};
-
 use aoc_runner_derive::{aoc, aoc_generator};
 
+use crate::{
+    solution::Solution,
+    utils::{Grid, ParseError},
+};
+
+use self::template::Template;
+
+pub mod aho_corasick;
+pub mod render;
+pub mod template;
+pub mod word_search;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct Input {
-    lines: Vec<Vec<char>>,
-    dim: (i64, i64),
+pub struct Input {
+    grid: Grid<char>,
 }
 
 impl Input {
-    fn at(&self, offset: Offset) -> Option<u8> {
-        let Offset(x, y) = offset;
-
-        self.lines
-            .get(y as usize)
-            .and_then(|line| line.get(x as usize).map(|c| *c as u8))
+    pub fn grid(&self) -> &Grid<char> {
+        &self.grid
     }
 }
 
 #[aoc_generator(day04)]
-fn generator(input: &str) -> Input {
-    let lines: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
-    let dim = (lines[0].len() as i64, lines.len() as i64);
-    Input { lines, dim }
+fn generator(input: &str) -> Result<Input, ParseError> {
+    Ok(Input {
+        grid: Grid::from_str(input, |c| c)?,
+    })
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct Offset(i64, i64);
+#[aoc(day04, part1)]
+fn part1(input: &Input) -> usize {
+    aho_corasick::count(&input.grid, &["XMAS"])
+}
 
-impl Mul<i64> for Offset {
-    type Output = Self;
+/// Two `MAS` crossing in the shape of an X, as searched for in part 2.
+pub fn x_mas_template() -> Template {
+    "M.S/.A./M.S"
+        .parse()
+        .expect("the X-MAS template should be valid")
+}
 
-    fn mul(self, rhs: i64) -> Self::Output {
-        let Offset(x, y) = self;
-        Offset(x * rhs, y * rhs)
-    }
+#[aoc(day04, part2)]
+fn part2(input: &Input) -> usize {
+    x_mas_template().find(&input.grid).len()
 }
 
-impl Add for Offset {
-    type Output = Self;
+pub struct Day04;
 
-    fn add(self, rhs: Self) -> Self::Output {
-        Offset(self.0 + rhs.0, self.1 + rhs.1)
-    }
-}
+impl Solution for Day04 {
+    const DAY: u32 = 4;
 
-#[aoc(day04, part1)]
-fn part1(input: &Input) -> usize {
-    let (width, height) = input.dim;
-
-    let mut xmas = 0;
-
-    let offsets = vec![
-        Offset(-1, 0),
-        Offset(1, 0),
-        Offset(0, -1),
-        Offset(0, 1),
-        Offset(-1, -1),
-        Offset(1, -1),
-        Offset(-1, 1),
-        Offset(1, 1),
-    ];
-
-    for x in 0..width {
-        for y in 0..height {
-            let index = Offset(x, y);
-            let Some(c) = input.at(index) else {
-                continue;
-            };
-
-            if c != b'X' {
-                continue;
-            }
-
-            for offset in &offsets {
-                let mut buffer = vec![];
-                for i in 1..=3 {
-                    if let Some(c) = input.at(index + *offset * i) {
-                        buffer.push(c);
-                    }
-                }
-
-                let text = String::from_utf8(buffer).unwrap();
-
-                if text == "MAS" {
-                    xmas += 1;
-                }
-            }
-        }
-    }
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    xmas
-}
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        generator(input)
+    }
 
-#[aoc(day04, part2)]
-fn part2(input: &Input) -> usize {
-    let (width, height) = input.dim;
-
-    let mut xmas = 0;
-
-    for x in 0..width {
-        for y in 0..height {
-            let index = Offset(x, y);
-            let Some(c) = input.at(index) else {
-                continue;
-            };
-
-            if c != b'A' {
-                continue;
-            }
-
-            let top_left = input.at(index + Offset(-1, -1));
-            let bottom_right = input.at(index + Offset(1, 1));
-            let first_diagonal = matches!(
-                (top_left, bottom_right),
-                (Some(b'M'), Some(b'S')) | (Some(b'S'), Some(b'M'))
-            );
-
-            let bottom_left = input.at(index + Offset(-1, 1));
-            let top_right = input.at(index + Offset(1, -1));
-            let second_diagonal = matches!(
-                (bottom_left, top_right),
-                (Some(b'M'), Some(b'S')) | (Some(b'S'), Some(b'M'))
-            );
-
-            if first_diagonal && second_diagonal {
-                xmas += 1;
-            }
-        }
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
     }
 
-    xmas
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
 }
 
 #[cfg(test)]
@@ -139,7 +75,7 @@ mod tests {
 
     use super::{generator, part1};
 
-    const INPUT: &str = "MMMSXXMASM
+    pub(super) const INPUT: &str = "MMMSXXMASM
 MSAMXMSMSA
 AMXSXMAAMM
 MSAMASMSMX
@@ -152,14 +88,14 @@ MXMXAXMASX";
 
     #[test]
     fn test_generator_part1() {
-        let gen = generator(INPUT);
+        let gen = generator(INPUT).unwrap();
 
-        assert_eq!(gen.dim, (10, 10));
+        assert_eq!((gen.grid.width(), gen.grid.height()), (10, 10));
     }
 
     #[test]
     fn test_part1() {
-        let gen = generator(INPUT);
+        let gen = generator(INPUT).unwrap();
 
         let output = part1(&gen);
 
@@ -168,7 +104,7 @@ MXMXAXMASX";
 
     #[test]
     fn test_part2() {
-        let gen = generator(INPUT);
+        let gen = generator(INPUT).unwrap();
 
         let output = part2(&gen);
 
@@ -0,0 +1,327 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::utils::{Coord, Grid};
+
+/// A pattern found by an [`AhoCorasick`] automaton.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    /// Index of the pattern the automaton was built from.
+    pub pattern: usize,
+    /// Index right after the last character of the match.
+    pub end: usize,
+}
+
+/// Aho–Corasick automaton finding all (overlapping) occurrences of several patterns in a single
+/// pass over a text.
+///
+/// The transitions form a complete table over the letters of the patterns, so every character of
+/// the text costs one lookup. Empty patterns are never found.
+#[derive(Debug, Clone)]
+pub struct AhoCorasick {
+    /// Symbol of every ASCII character, grid letters almost always are.
+    ascii: [usize; 128],
+    alphabet: HashMap<char, usize>,
+    /// Number of columns of `transitions`: one per letter, plus one for every other character.
+    symbols: usize,
+    transitions: Vec<usize>,
+    /// Patterns ending at each state, including the ones reached through suffix links.
+    outputs: Vec<Vec<usize>>,
+}
+
+const ROOT: usize = 0;
+
+impl AhoCorasick {
+    pub fn new(patterns: &[&str]) -> Self {
+        let mut alphabet = HashMap::new();
+        for c in patterns.iter().flat_map(|pattern| pattern.chars()) {
+            let next = alphabet.len();
+            alphabet.entry(c).or_insert(next);
+        }
+        let symbols = alphabet.len() + 1;
+
+        let mut ascii = [symbols - 1; 128];
+        for (&c, &symbol) in &alphabet {
+            if c.is_ascii() {
+                ascii[c as usize] = symbol;
+            }
+        }
+
+        // build the trie, `None` marks a missing edge
+        let mut trie = vec![vec![None; symbols]];
+        let mut outputs = vec![vec![]];
+
+        for (index, pattern) in patterns.iter().enumerate() {
+            if pattern.is_empty() {
+                continue;
+            }
+
+            let mut state = ROOT;
+            for c in pattern.chars() {
+                let symbol = alphabet[&c];
+                state = match trie[state][symbol] {
+                    Some(next) => next,
+                    None => {
+                        trie.push(vec![None; symbols]);
+                        outputs.push(vec![]);
+                        trie[state][symbol] = Some(trie.len() - 1);
+                        trie.len() - 1
+                    }
+                };
+            }
+            outputs[state].push(index);
+        }
+
+        // complete the transitions in breadth first order, so the suffix link of every state is
+        // finished before the state itself
+        let mut transitions = vec![ROOT; trie.len() * symbols];
+        let mut suffix = vec![ROOT; trie.len()];
+        let mut queue = VecDeque::new();
+
+        for symbol in 0..symbols {
+            if let Some(next) = trie[ROOT][symbol] {
+                transitions[symbol] = next;
+                queue.push_back(next);
+            }
+        }
+
+        while let Some(state) = queue.pop_front() {
+            for symbol in 0..symbols {
+                let fallback = transitions[suffix[state] * symbols + symbol];
+
+                match trie[state][symbol] {
+                    Some(next) => {
+                        suffix[next] = fallback;
+                        let inherited = outputs[fallback].clone();
+                        outputs[next].extend(inherited);
+
+                        transitions[state * symbols + symbol] = next;
+                        queue.push_back(next);
+                    }
+                    None => transitions[state * symbols + symbol] = fallback,
+                }
+            }
+        }
+
+        AhoCorasick {
+            ascii,
+            alphabet,
+            symbols,
+            transitions,
+            outputs,
+        }
+    }
+
+    /// All occurrences of all patterns in `text`, ordered by where they end.
+    pub fn find_iter<'a>(
+        &'a self,
+        text: impl IntoIterator<Item = char> + 'a,
+    ) -> impl Iterator<Item = Match> + 'a {
+        let mut state = ROOT;
+
+        text.into_iter().enumerate().flat_map(move |(i, c)| {
+            state = self.step(state, c);
+
+            self.outputs[state].iter().map(move |&pattern| Match {
+                pattern,
+                end: i + 1,
+            })
+        })
+    }
+
+    /// Number of occurrences of all patterns in `text`.
+    pub fn count(&self, text: impl IntoIterator<Item = char>) -> usize {
+        self.count_symbols(text.into_iter().map(|c| self.symbol(c)))
+    }
+
+    /// Like [`AhoCorasick::count`], for a text already translated with [`AhoCorasick::symbol`].
+    pub fn count_symbols(&self, symbols: impl IntoIterator<Item = usize>) -> usize {
+        let mut state = ROOT;
+
+        symbols
+            .into_iter()
+            .map(|symbol| {
+                state = self.transitions[state * self.symbols + symbol];
+                self.outputs[state].len()
+            })
+            .sum()
+    }
+
+    /// The column of `c` in the transition table. All characters that do not occur in any
+    /// pattern share the last one.
+    pub fn symbol(&self, c: char) -> usize {
+        if c.is_ascii() {
+            self.ascii[c as usize]
+        } else {
+            self.alphabet.get(&c).copied().unwrap_or(self.symbols - 1)
+        }
+    }
+
+    fn step(&self, state: usize, c: char) -> usize {
+        self.transitions[state * self.symbols + self.symbol(c)]
+    }
+}
+
+/// Every row, column and diagonal of `grid`, read left to right and top to bottom.
+pub fn lines<T: Copy>(grid: &Grid<T>) -> Vec<Vec<T>> {
+    let (width, height) = grid.dim();
+    let rows = grid.rows().collect::<Vec<_>>();
+
+    // walk down from `start`, moving `dx` columns per row
+    let diagonal = |Coord(x, y): Coord, dx: i64| {
+        rows[y as usize..]
+            .iter()
+            .zip((0..).map(|i| x + dx * i))
+            .map_while(|(row, x)| usize::try_from(x).ok().and_then(|x| row.get(x).copied()))
+            .collect::<Vec<_>>()
+    };
+
+    let mut lines = rows.iter().map(|row| row.to_vec()).collect::<Vec<_>>();
+    lines.extend(grid.columns().map(|column| column.copied().collect()));
+
+    // diagonals start on the top edge, or on the left (or right) edge below the first row
+    let tops = (0..width as i64).map(|x| Coord(x, 0));
+    for start in tops.clone().chain((1..height as i64).map(|y| Coord(0, y))) {
+        lines.push(diagonal(start, 1));
+    }
+    for start in tops.chain((1..height as i64).map(|y| Coord(width as i64 - 1, y))) {
+        lines.push(diagonal(start, -1));
+    }
+
+    lines
+}
+
+/// Count the occurrences of `words` in all eight directions of `grid`, like
+/// [`super::word_search::search`] with [`super::word_search::Directions::ALL`].
+///
+/// The lines of the grid are only read forwards, the reversed words find the backwards matches.
+/// Like in the word search, palindromes are counted once per direction and single letters once
+/// per cell.
+pub fn count(grid: &Grid<char>, words: &[&str]) -> usize {
+    let (letters, words): (Vec<&str>, Vec<&str>) =
+        words.iter().partition(|word| word.chars().count() == 1);
+
+    // single letters have no direction, so they are not looked for on the lines
+    let cells = letters
+        .iter()
+        .flat_map(|letter| letter.chars())
+        .map(|letter| grid.positions(&letter).count())
+        .sum::<usize>();
+
+    let reversed = words
+        .iter()
+        .map(|word| word.chars().rev().collect::<String>())
+        .collect::<Vec<_>>();
+    let patterns = words
+        .iter()
+        .copied()
+        .chain(reversed.iter().map(String::as_str))
+        .collect::<Vec<_>>();
+
+    let automaton = AhoCorasick::new(&patterns);
+
+    // a line of symbols is a quarter the size of a line of chars, as long as they fit in a byte
+    let on_lines = if automaton.symbols > usize::from(u8::MAX) + 1 {
+        lines(grid)
+            .into_iter()
+            .map(|line| automaton.count(line))
+            .sum::<usize>()
+    } else {
+        let (width, _) = grid.dim();
+        let symbols = grid
+            .iter()
+            .map(|(_, &c)| automaton.symbol(c) as u8)
+            .collect();
+
+        lines(&Grid::new(symbols, width))
+            .into_iter()
+            .map(|line| automaton.count_symbols(line.into_iter().map(usize::from)))
+            .sum()
+    };
+
+    cells + on_lines
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        day_04::word_search::{search, Directions},
+        utils::{lcg::Lcg, Grid},
+    };
+
+    use super::{count, lines, AhoCorasick, Match};
+
+    #[test]
+    fn test_find_iter() {
+        let automaton = AhoCorasick::new(&["he", "she", "his", "hers", ""]);
+        let matches = automaton.find_iter("ushers".chars()).collect::<Vec<_>>();
+
+        assert_eq!(
+            matches,
+            vec![
+                Match { pattern: 1, end: 4 },
+                Match { pattern: 0, end: 4 },
+                Match { pattern: 3, end: 6 }
+            ]
+        );
+        assert_eq!(automaton.count("ahishers".chars()), 4);
+    }
+
+    #[test]
+    fn test_overlapping() {
+        let automaton = AhoCorasick::new(&["aa", "a"]);
+
+        assert_eq!(automaton.count("aaa".chars()), 5);
+        assert_eq!(automaton.count("xax".chars()), 1);
+    }
+
+    #[test]
+    fn test_lines() {
+        let grid = Grid::from_str("ABC\nDEF", |c| c).unwrap();
+        let lines = lines(&grid)
+            .into_iter()
+            .map(String::from_iter)
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            lines,
+            vec!["ABC", "DEF", "AD", "BE", "CF", "AE", "BF", "C", "D", "A", "BD", "CE", "F"]
+        );
+    }
+
+    #[test]
+    fn test_count_matches_word_search() {
+        // with only a few letters many words overlap
+        let mut rng = Lcg::new(5);
+
+        let words = ["XMAS", "MAS", "AXA", "SAMX", "XX", "M", "", "A", "M"];
+
+        for _ in 0..20 {
+            let width = rng.below(12) as usize + 1;
+            let height = rng.below(12) as usize + 1;
+            let cells = (0..width * height)
+                .map(|_| *rng.pick(&['X', 'M', 'A', 'S']))
+                .collect();
+            let grid = Grid::new(cells, width);
+
+            assert_eq!(
+                count(&grid, &words),
+                search(&grid, &words, Directions::ALL).len(),
+                "{grid:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_count_large_alphabet() {
+        // more letters than fit in a byte
+        let letters = (0..300)
+            .filter_map(|i| char::from_u32(0x100 + i))
+            .collect::<String>();
+        let grid = Grid::new(letters.chars().collect(), 30);
+
+        let start = letters.chars().take(6).collect::<String>();
+        let words = [start.as_str(), &letters];
+        assert_eq!(count(&grid, &words), 1);
+        assert_eq!(search(&grid, &words, Directions::ALL).len(), 1);
+    }
+}
@@ -0,0 +1,199 @@
+use crate::utils::{
+    ansi::{paint, Color},
+    Coord, Direction8, Grid,
+};
+
+use super::{
+    template::{Template, TemplateMatch},
+    word_search::{search, wrap, Directions, WordMatch},
+    x_mas_template,
+};
+
+/// Why a cell is highlighted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Mark {
+    /// Part of a word read in this direction.
+    Word(Direction8),
+    /// Part of a template match, like an X-MAS.
+    Template,
+}
+
+impl Mark {
+    fn color(self) -> Color {
+        match self {
+            Mark::Word(Direction8::Up) => Color::RED,
+            Mark::Word(Direction8::UpRight) => Color::YELLOW,
+            Mark::Word(Direction8::Right) => Color::GREEN,
+            Mark::Word(Direction8::DownRight) => Color::CYAN,
+            Mark::Word(Direction8::Down) => Color::BLUE,
+            Mark::Word(Direction8::DownLeft) => Color::MAGENTA,
+            Mark::Word(Direction8::Left) => Color::BRIGHT_RED,
+            Mark::Word(Direction8::UpLeft) => Color::BRIGHT_BLUE,
+            Mark::Template => Color::BOLD_WHITE,
+        }
+    }
+}
+
+/// A grid where only the cells of some matches are shown, like the illustrations in the puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Highlight<'a> {
+    grid: &'a Grid<char>,
+    marks: Grid<Option<Mark>>,
+}
+
+impl<'a> Highlight<'a> {
+    /// A highlight without any marked cells.
+    pub fn new(grid: &'a Grid<char>) -> Self {
+        let (width, height) = grid.dim();
+
+        Highlight {
+            grid,
+            marks: Grid::new(vec![None; width * height], width),
+        }
+    }
+
+    /// The matches of part 1.
+    pub fn xmas(grid: &'a Grid<char>) -> Self {
+        let mut highlight = Highlight::new(grid);
+        highlight.mark_words(&search(grid, &["XMAS"], Directions::ALL));
+        highlight
+    }
+
+    /// The matches of part 2.
+    pub fn x_mas(grid: &'a Grid<char>) -> Self {
+        let x_mas = x_mas_template();
+
+        let mut highlight = Highlight::new(grid);
+        highlight.mark_template(&x_mas, &x_mas.find(grid));
+        highlight
+    }
+
+    /// Mark the cells of `matches`, wrapping them back into the grid if necessary. Cells that
+    /// are already marked keep their mark.
+    pub fn mark_words(&mut self, matches: &[WordMatch]) {
+        for word in matches {
+            for cell in word.cells() {
+                self.mark(wrap(cell, self.grid.dim()), Mark::Word(word.direction));
+            }
+        }
+    }
+
+    /// Mark the non-wildcard cells of `matches` of `template`. Cells that are already marked keep
+    /// their mark.
+    pub fn mark_template(&mut self, template: &Template, matches: &[TemplateMatch]) {
+        let variants = template.variants();
+
+        for found in matches {
+            for (offset, _) in variants[found.variant].cells() {
+                self.mark(found.position + offset, Mark::Template);
+            }
+        }
+    }
+
+    fn mark(&mut self, coord: Coord, mark: Mark) {
+        if let Some(cell) = self.marks.get_mut(coord) {
+            cell.get_or_insert(mark);
+        }
+    }
+
+    pub fn get(&self, coord: Coord) -> Option<Mark> {
+        self.marks.get(coord).copied().flatten()
+    }
+
+    /// Render the grid with every unmarked cell replaced by a `.`.
+    pub fn to_plain(&self) -> String {
+        self.render(|c, _| c.to_string(), ".")
+    }
+
+    /// Like [`Highlight::to_plain`], but color every marked cell by the direction of its word
+    /// and dim the blanks.
+    pub fn to_terminal(&self) -> String {
+        self.render(
+            |c, mark| paint(&c.to_string(), mark.color()),
+            &paint(".", Color::DIM),
+        )
+    }
+
+    fn render(&self, marked: impl Fn(char, Mark) -> String, blank: &str) -> String {
+        self.grid
+            .rows()
+            .zip(self.marks.rows())
+            .map(|(cells, marks)| {
+                cells
+                    .iter()
+                    .zip(marks)
+                    .map(|(&c, mark)| match mark {
+                        Some(mark) => marked(c, *mark),
+                        None => blank.to_string(),
+                    })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        day_04::tests::INPUT,
+        utils::{Coord, Direction8, Grid},
+    };
+
+    use super::{Highlight, Mark};
+
+    #[test]
+    fn test_xmas() {
+        let grid = Grid::from_str(INPUT, |c| c).unwrap();
+        let highlight = Highlight::xmas(&grid);
+
+        assert_eq!(
+            highlight.to_plain(),
+            "....XXMAS.
+.SAMXMS...
+...S..A...
+..A.A.MS.X
+XMASAMX.MM
+X.....XA.A
+S.S.S.S.SS
+.A.A.A.A.A
+..M.M.M.MM
+.X.X.XMASX"
+        );
+        assert_eq!(
+            highlight.get(Coord(5, 0)),
+            Some(Mark::Word(Direction8::Right))
+        );
+        assert_eq!(highlight.get(Coord(0, 0)), None);
+    }
+
+    #[test]
+    fn test_x_mas() {
+        let grid = Grid::from_str(INPUT, |c| c).unwrap();
+
+        assert_eq!(
+            Highlight::x_mas(&grid).to_plain(),
+            ".M.S......
+..A..MSMS.
+.M.S.MAA..
+..A.ASMSM.
+.M.S.M....
+..........
+S.S.S.S.S.
+.A.A.A.A..
+M.M.M.M.M.
+.........."
+        );
+    }
+
+    #[test]
+    fn test_to_terminal() {
+        let grid = Grid::from_str("SAMX\nXXXX", |c| c).unwrap();
+
+        assert_eq!(
+            Highlight::xmas(&grid).to_terminal(),
+            "\x1b[91mS\x1b[0m\x1b[91mA\x1b[0m\x1b[91mM\x1b[0m\x1b[91mX\x1b[0m\n\x1b[2m.\x1b[0m\x1b[2m.\x1b[0m\x1b[2m.\x1b[0m\x1b[2m.\x1b[0m"
+        );
+        assert_eq!(Highlight::new(&grid).to_plain(), "....\n....");
+    }
+}
@@ -0,0 +1,246 @@
+use std::str::FromStr;
+
+use crate::utils::{Coord, Grid, ParseError};
+
+/// A small 2D pattern to look for in a grid, where `None` matches any cell.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Template {
+    cells: Grid<Option<char>>,
+}
+
+impl FromStr for Template {
+    type Err = ParseError;
+
+    /// Parse rows separated by `/` or newlines, where `.` is a wildcard, e.g. `M.S/.A./M.S`.
+    /// Whitespace around the rows is ignored. All rows have to be of the same, non-zero width.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rows = s
+            .trim_end_matches('\n')
+            .split(['/', '\n'])
+            .map(str::trim)
+            .collect::<Vec<_>>();
+
+        let width = rows[0].chars().count();
+        if width == 0 {
+            return Err(ParseError::missing(s, rows[0], "a letter or `.`"));
+        }
+
+        for row in &rows[1..] {
+            match row.char_indices().nth(width) {
+                Some((i, _)) => return Err(ParseError::invalid(s, &row[i..], "end of row")),
+                None if row.chars().count() < width => {
+                    return Err(ParseError::missing(s, row, "a letter or `.`"))
+                }
+                None => {}
+            }
+        }
+
+        let cells = Grid::from_str(&rows.join("\n"), |c| (c != '.').then_some(c))?;
+
+        Ok(Template { cells })
+    }
+}
+
+/// A placement of one variant of a [`Template`] in a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TemplateMatch {
+    /// Position of the top left corner of the variant in the grid.
+    pub position: Coord,
+    /// Index into [`Template::variants`].
+    pub variant: usize,
+}
+
+impl Template {
+    pub fn dim(&self) -> (usize, usize) {
+        self.cells.dim()
+    }
+
+    /// The cells that are not wildcards, relative to the top left corner.
+    pub fn cells(&self) -> impl Iterator<Item = (Coord, char)> + '_ {
+        self.cells
+            .iter()
+            .filter_map(|(coord, cell)| cell.map(|c| (coord, c)))
+    }
+
+    /// Rotate clockwise by 90 degrees.
+    pub fn rotate(&self) -> Template {
+        let (width, height) = self.dim();
+        let cells = (0..width)
+            .flat_map(|y| (0..height).map(move |x| Coord(y as i64, (height - 1 - x) as i64)))
+            .map(|coord| self.cells[coord])
+            .collect();
+
+        Template {
+            cells: Grid::new(cells, height),
+        }
+    }
+
+    /// Mirror along the vertical axis.
+    pub fn reflect(&self) -> Template {
+        let (width, height) = self.dim();
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| Coord((width - 1 - x) as i64, y as i64)))
+            .map(|coord| self.cells[coord])
+            .collect();
+
+        Template {
+            cells: Grid::new(cells, width),
+        }
+    }
+
+    /// All distinct rotations and reflections, starting with the template itself.
+    pub fn variants(&self) -> Vec<Template> {
+        let mut variants: Vec<Template> = vec![];
+        let mut current = self.clone();
+
+        for _ in 0..2 {
+            for _ in 0..4 {
+                if !variants.contains(&current) {
+                    variants.push(current.clone());
+                }
+                current = current.rotate();
+            }
+            current = current.reflect();
+        }
+
+        variants
+    }
+
+    /// Whether this variant matches `grid` with its top left corner at `position`.
+    fn matches_at(&self, grid: &Grid<char>, position: Coord) -> bool {
+        self.cells()
+            .all(|(coord, c)| grid.get(position + coord) == Some(&c))
+    }
+
+    /// Find every placement of every variant in `grid`.
+    pub fn find(&self, grid: &Grid<char>) -> Vec<TemplateMatch> {
+        let (grid_width, grid_height) = grid.dim();
+        let mut matches = vec![];
+
+        for (index, variant) in self.variants().iter().enumerate() {
+            let (width, height) = variant.dim();
+            if width > grid_width || height > grid_height {
+                continue;
+            }
+
+            for y in 0..=grid_height - height {
+                for x in 0..=grid_width - width {
+                    let position = Coord(x as i64, y as i64);
+
+                    if variant.matches_at(grid, position) {
+                        matches.push(TemplateMatch {
+                            position,
+                            variant: index,
+                        });
+                    }
+                }
+            }
+        }
+
+        matches
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        day_04::{tests::INPUT, x_mas_template},
+        utils::{Coord, Grid, ParseError},
+    };
+
+    use super::{Template, TemplateMatch};
+
+    #[test]
+    fn test_parse() {
+        let template = "M.S / .A. / M.S".parse::<Template>().unwrap();
+
+        assert_eq!(template.dim(), (3, 3));
+        assert_eq!(
+            template.cells().collect::<Vec<_>>(),
+            vec![
+                (Coord(0, 0), 'M'),
+                (Coord(2, 0), 'S'),
+                (Coord(1, 1), 'A'),
+                (Coord(0, 2), 'M'),
+                (Coord(2, 2), 'S')
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_trailing_newline() {
+        assert_eq!(
+            "M.S\n.A.\nM.S\n".parse::<Template>(),
+            "M.S/.A./M.S".parse::<Template>()
+        );
+        assert!("M.S\n.A.\nM.S\n".parse::<Template>().is_ok());
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert_eq!(
+            "".parse::<Template>(),
+            Err(ParseError::MissingToken {
+                line: 1,
+                column: 1,
+                expected: "a letter or `.`"
+            })
+        );
+        assert_eq!(
+            "AB/C".parse::<Template>(),
+            Err(ParseError::MissingToken {
+                line: 1,
+                column: 5,
+                expected: "a letter or `.`"
+            })
+        );
+        assert_eq!(
+            "AB / CDE".parse::<Template>(),
+            Err(ParseError::InvalidToken {
+                line: 1,
+                column: 8,
+                token: "E".into(),
+                expected: "end of row"
+            })
+        );
+        assert!("A/".parse::<Template>().is_err());
+        assert!("A\nBC".parse::<Template>().is_err());
+    }
+
+    #[test]
+    fn test_variants() {
+        let template = "AB".parse::<Template>().unwrap();
+        assert_eq!(template.rotate(), "A/B".parse().unwrap());
+        assert_eq!(template.reflect(), "BA".parse().unwrap());
+        assert_eq!(template.variants().len(), 4);
+
+        let l = "A./BC".parse::<Template>().unwrap();
+        assert_eq!(l.rotate(), "BA/C.".parse().unwrap());
+        assert_eq!(l.variants().len(), 8);
+        assert_eq!("A./AA".parse::<Template>().unwrap().variants().len(), 4);
+
+        assert_eq!("AB/CD".parse::<Template>().unwrap().variants().len(), 8);
+        assert_eq!(x_mas_template().variants().len(), 4);
+        assert_eq!("A".parse::<Template>().unwrap().variants().len(), 1);
+    }
+
+    #[test]
+    fn test_find() {
+        let grid = Grid::from_str(INPUT, |c| c).unwrap();
+        let x_mas = x_mas_template();
+
+        let matches = x_mas.find(&grid);
+        assert_eq!(matches.len(), 9);
+        assert!(matches.contains(&TemplateMatch {
+            position: Coord(1, 0),
+            variant: 0
+        }));
+
+        // straight lines are templates as well, so this is part 1 without diagonals
+        let xmas = "XMAS".parse::<Template>().unwrap();
+        assert_eq!(xmas.find(&grid).len(), 8);
+
+        let too_large = "XMASXMASXMAS".parse::<Template>().unwrap();
+        assert!(too_large.find(&grid).is_empty());
+    }
+}
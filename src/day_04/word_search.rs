@@ -0,0 +1,270 @@
+use crate::utils::{Coord, Direction8, Grid};
+
+/// The directions a word search looks in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Directions {
+    /// Up, down, left and right.
+    pub orthogonal: bool,
+    /// The four diagonals.
+    pub diagonal: bool,
+    /// Words may leave the grid on one edge and continue on the opposite one. They are never
+    /// longer than the line they are on, so no cell is used twice.
+    pub wrap_around: bool,
+    /// Palindromes read the same in opposite directions, so they are found twice on the same
+    /// cells. Only report them once, in the direction that comes first in [`Direction8::ALL`].
+    pub palindromes_once: bool,
+}
+
+impl Directions {
+    /// All eight directions without wrapping, as in the puzzle.
+    pub const ALL: Directions = Directions {
+        orthogonal: true,
+        diagonal: true,
+        wrap_around: false,
+        palindromes_once: false,
+    };
+
+    pub const ORTHOGONAL: Directions = Directions {
+        diagonal: false,
+        ..Directions::ALL
+    };
+
+    pub const DIAGONAL: Directions = Directions {
+        orthogonal: false,
+        ..Directions::ALL
+    };
+
+    pub fn iter(&self) -> impl Iterator<Item = Direction8> + '_ {
+        Direction8::all().filter(|direction| {
+            if direction.is_diagonal() {
+                self.diagonal
+            } else {
+                self.orthogonal
+            }
+        })
+    }
+}
+
+/// A word found in the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct WordMatch {
+    /// Index of the word in the searched dictionary.
+    pub word: usize,
+    pub start: Coord,
+    pub direction: Direction8,
+    pub len: usize,
+}
+
+impl WordMatch {
+    /// The cells of the match in reading order. For wrapped matches, these are outside of the
+    /// grid and have to be wrapped with [`wrap`].
+    pub fn cells(&self) -> impl Iterator<Item = Coord> + '_ {
+        (0..self.len as i64).map(|i| self.start + self.direction.offset() * i)
+    }
+}
+
+/// Move `coord` back into a grid of size `dim`, continuing on the opposite edge.
+pub fn wrap(coord: Coord, (width, height): (usize, usize)) -> Coord {
+    Coord(
+        coord.0.rem_euclid(width as i64),
+        coord.1.rem_euclid(height as i64),
+    )
+}
+
+/// Find every occurrence of every word in `grid`.
+///
+/// Words of a single letter have no direction, they are reported once per cell with the first
+/// enabled direction, and not at all without any. Empty words are never found.
+pub fn search(grid: &Grid<char>, words: &[&str], directions: Directions) -> Vec<WordMatch> {
+    let mut matches = vec![];
+
+    for (index, word) in words.iter().enumerate() {
+        let chars = word.chars().collect::<Vec<_>>();
+        let Some(first) = chars.first() else {
+            continue;
+        };
+        let palindrome = chars.iter().eq(chars.iter().rev());
+
+        for start in grid.positions(first) {
+            if chars.len() == 1 {
+                if let Some(direction) = directions.iter().next() {
+                    matches.push(WordMatch {
+                        word: index,
+                        start,
+                        direction,
+                        len: 1,
+                    });
+                }
+                continue;
+            }
+
+            for direction in directions.iter() {
+                if directions.palindromes_once && palindrome && direction as usize >= 4 {
+                    continue;
+                }
+
+                if directions.wrap_around && chars.len() > line_len(direction, grid.dim()) {
+                    continue;
+                }
+
+                let candidate = WordMatch {
+                    word: index,
+                    start,
+                    direction,
+                    len: chars.len(),
+                };
+
+                let found = candidate.cells().zip(&chars).skip(1).all(|(cell, c)| {
+                    let cell = if directions.wrap_around {
+                        wrap(cell, grid.dim())
+                    } else {
+                        cell
+                    };
+
+                    grid.get(cell) == Some(c)
+                });
+
+                if found {
+                    matches.push(candidate);
+                }
+            }
+        }
+    }
+
+    matches
+}
+
+/// How many cells a wrapped line in `direction` visits before it returns to its start.
+fn line_len(direction: Direction8, (width, height): (usize, usize)) -> usize {
+    let Coord(x, y) = direction.offset();
+
+    match (x, y) {
+        (0, _) => height,
+        (_, 0) => width,
+        _ => width / gcd(width, height) * height,
+    }
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        day_04::tests::INPUT,
+        utils::{Coord, Direction8, Grid},
+    };
+
+    use super::{search, Directions, WordMatch};
+
+    fn grid(input: &str) -> Grid<char> {
+        Grid::from_str(input, |c| c).unwrap()
+    }
+
+    #[test]
+    fn test_search_xmas() {
+        let grid = grid(INPUT);
+
+        assert_eq!(search(&grid, &["XMAS"], Directions::ALL).len(), 18);
+        assert_eq!(search(&grid, &["XMAS"], Directions::ORTHOGONAL).len(), 8);
+        assert_eq!(search(&grid, &["XMAS"], Directions::DIAGONAL).len(), 10);
+        assert!(
+            search(&grid, &["XMAS"], Directions::ALL).contains(&WordMatch {
+                word: 0,
+                start: Coord(5, 0),
+                direction: Direction8::Right,
+                len: 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_search_dictionary() {
+        let grid = grid("CAT\nOXX\nDOG");
+        let matches = search(
+            &grid,
+            &["CAT", "DOG", "COD", "TAC", "", "COW"],
+            Directions::ALL,
+        );
+
+        let found = matches.iter().map(|m| m.word).collect::<Vec<_>>();
+        assert_eq!(found, vec![0, 1, 2, 3]);
+        assert_eq!(matches[2].direction, Direction8::Down);
+        assert_eq!(matches[3].start, Coord(2, 0));
+    }
+
+    #[test]
+    fn test_search_palindromes() {
+        let grid = grid("ABA\nXBX\nABA");
+
+        assert_eq!(search(&grid, &["ABA"], Directions::ALL).len(), 8);
+
+        let once = Directions {
+            palindromes_once: true,
+            ..Directions::ALL
+        };
+        let matches = search(&grid, &["ABA"], once);
+        assert_eq!(matches.len(), 4);
+        assert!(matches.iter().all(|m| (m.direction as usize) < 4));
+
+        assert_eq!(search(&grid, &["B"], Directions::ALL).len(), 3);
+    }
+
+    #[test]
+    fn test_search_single_letter() {
+        let grid = grid("AB\nBA");
+
+        assert_eq!(
+            search(&grid, &["A"], Directions::DIAGONAL),
+            vec![
+                WordMatch {
+                    word: 0,
+                    start: Coord(0, 0),
+                    direction: Direction8::UpRight,
+                    len: 1
+                },
+                WordMatch {
+                    word: 0,
+                    start: Coord(1, 1),
+                    direction: Direction8::UpRight,
+                    len: 1
+                }
+            ]
+        );
+
+        let none = Directions {
+            diagonal: false,
+            ..Directions::DIAGONAL
+        };
+        assert!(search(&grid, &["A"], none).is_empty());
+    }
+
+    #[test]
+    fn test_search_wrap_around() {
+        let grid = grid("ASXM\nXXXX");
+        let wrapping = Directions {
+            wrap_around: true,
+            ..Directions::ORTHOGONAL
+        };
+
+        assert!(search(&grid, &["XMAS"], Directions::ORTHOGONAL).is_empty());
+        assert_eq!(
+            search(&grid, &["XMAS"], wrapping),
+            vec![WordMatch {
+                word: 0,
+                start: Coord(2, 0),
+                direction: Direction8::Right,
+                len: 4
+            }]
+        );
+
+        // a word may not wrap onto its own cells
+        assert!(search(&grid, &["XMASX"], wrapping).is_empty());
+        assert_eq!(search(&grid, &["XX"], wrapping).len(), 12);
+    }
+}
@@ -5,39 +5,46 @@ use std::{
 
 use aoc_runner_derive::{aoc, aoc_generator};
 
+use crate::{
+    solution::Solution,
+    utils::{parse::parse_token, ParseError},
+};
+
 #[derive(Clone, Debug, PartialEq, Eq)]
-struct Input {
+pub struct Input {
     rules: Vec<(u64, u64)>,
     updates: Vec<Vec<u64>>,
 }
 
 #[aoc_generator(day5)]
-fn generator(input: &str) -> Input {
-    let sections = input.split("\n\n").collect::<Vec<_>>();
-    let rules = sections[0];
-    let updates = sections[1];
+fn generator(input: &str) -> Result<Input, ParseError> {
+    let (rules, updates) = input.split_once("\n\n").ok_or_else(|| {
+        ParseError::missing(input, input, "an empty line followed by the updates")
+    })?;
 
     let rules = rules
         .lines()
-        .map(|lines| {
-            let parts = lines.split("|").collect::<Vec<_>>();
-            (
-                parts[0].parse::<u64>().unwrap(),
-                parts[1].parse::<u64>().unwrap(),
-            )
+        .map(|line| {
+            let (left, right) = line
+                .split_once("|")
+                .ok_or_else(|| ParseError::missing(input, line, "`|` between two pages"))?;
+            Ok((
+                parse_token(input, left, "a page number")?,
+                parse_token(input, right, "a page number")?,
+            ))
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
 
     let updates = updates
         .lines()
         .map(|line| {
             line.split(",")
-                .map(|update| update.parse::<u64>().unwrap())
+                .map(|update| parse_token(input, update, "a page number"))
                 .collect()
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
 
-    Input { rules, updates }
+    Ok(Input { rules, updates })
 }
 
 #[aoc(day5, part1)]
@@ -142,10 +149,32 @@ fn part2(Input { rules, updates }: &Input) -> u64 {
     sum
 }
 
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u32 = 5;
+
+    type Input = Input;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        generator(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
-    use crate::day_05::part2;
+    use crate::{day_05::part2, utils::ParseError};
 
     use super::{generator, part1};
 
@@ -180,7 +209,7 @@ mod tests {
 
     #[test]
     fn test_generator() {
-        let gen = generator(INPUT);
+        let gen = generator(INPUT).unwrap();
 
         assert_eq!(gen.rules[0], (47, 53));
         assert_eq!(gen.updates[0], vec![75, 47, 61, 53, 29]);
@@ -188,7 +217,7 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        let gen = generator(INPUT);
+        let gen = generator(INPUT).unwrap();
         let res = part1(&gen);
 
         assert_eq!(res, 143);
@@ -196,9 +225,38 @@ mod tests {
 
     #[test]
     fn test_part2() {
-        let gen = generator(INPUT);
+        let gen = generator(INPUT).unwrap();
         let res = part2(&gen);
 
         assert_eq!(res, 123);
     }
+
+    #[test]
+    fn test_generator_malformed() {
+        assert_eq!(
+            generator("47|53\n97-13\n\n75,47"),
+            Err(ParseError::MissingToken {
+                line: 2,
+                column: 6,
+                expected: "`|` between two pages"
+            })
+        );
+        assert_eq!(
+            generator("47|53\n\n75,47,,53"),
+            Err(ParseError::InvalidToken {
+                line: 3,
+                column: 7,
+                token: "".into(),
+                expected: "a page number"
+            })
+        );
+        assert_eq!(
+            generator("47|53"),
+            Err(ParseError::MissingToken {
+                line: 1,
+                column: 6,
+                expected: "an empty line followed by the updates"
+            })
+        );
+    }
 }
@@ -2,43 +2,32 @@ use std::collections::HashSet;
 
 use aoc_runner_derive::{aoc, aoc_generator};
 
-use crate::utils::Coord;
+use crate::{
+    solution::Solution,
+    utils::{Coord, Direction4, Grid, ParseError},
+};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct Map(Vec<Vec<char>>);
+struct Map(Grid<char>);
 
 impl Map {
-    fn contains(&self, Coord(x, y): Coord) -> bool {
-        let map = &self.0;
-        y < (map.len() as i64) && y >= 0 && x < (map[0].len() as i64) && x >= 0
+    fn contains(&self, coord: Coord) -> bool {
+        self.0.contains(coord)
     }
 
     fn is_wall(&self, coord: Coord) -> bool {
-        let Some(c) = self.get(coord) else {
-            return false;
-        };
-
-        c == '#'
-    }
-
-    fn get(&self, Coord(x, y): Coord) -> Option<char> {
-        self.0
-            .get(y as usize)
-            .and_then(|map| map.get(x as usize))
-            .cloned()
+        self.0.get(coord) == Some(&'#')
     }
 
-    fn mark(&mut self, Coord(x, y): Coord) {
-        let Some(c) = self.0.get_mut(y as usize) else {
-            return;
-        };
-
-        c[x as usize] = 'X';
+    fn mark(&mut self, coord: Coord) {
+        if let Some(c) = self.0.get_mut(coord) {
+            *c = 'X';
+        }
     }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct Input {
+pub struct Input {
     map: Map,
     guard: Guard,
 }
@@ -46,88 +35,32 @@ struct Input {
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct Guard {
     position: Coord,
-    direction: Direction,
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Direction {
-    Up,
-    Down,
-    Right,
-    Left,
-}
-
-impl Direction {
-    fn turn(&self) -> Direction {
-        use Direction::*;
-
-        match self {
-            Up => Right,
-            Down => Left,
-            Right => Down,
-            Left => Up,
-        }
-    }
-
-    fn offset(&self) -> Coord {
-        use Direction::*;
-
-        match self {
-            Up => Coord(0, -1),
-            Down => Coord(0, 1),
-            Right => Coord(1, 0),
-            Left => Coord(-1, 0),
-        }
-    }
+    direction: Direction4,
 }
 
 #[aoc_generator(day6)]
-fn generator(input: &str) -> Input {
-    let map: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
-
-    let width = map[0].len();
-    let height = map.len();
+fn generator(input: &str) -> Result<Input, ParseError> {
+    let map = Grid::from_str(input, |c| c)?;
 
     let mut guard = None;
-    for x in 0..width {
-        for y in 0..height {
-            let position = Coord(x as i64, y as i64);
-            let c = map[y][x];
-
-            match c {
-                '^' => {
-                    guard = Some(Guard {
-                        position,
-                        direction: Direction::Up,
-                    });
-                }
-                '>' => {
-                    guard = Some(Guard {
-                        position,
-                        direction: Direction::Right,
-                    })
-                }
-                'v' => {
-                    guard = Some(Guard {
-                        position,
-                        direction: Direction::Down,
-                    })
-                }
-                '<' => {
-                    guard = Some(Guard {
-                        position,
-                        direction: Direction::Left,
-                    })
-                }
-                _ => {}
-            }
-        }
+    for (position, c) in map.iter() {
+        let Some(direction) = Direction4::from_arrow(*c) else {
+            continue;
+        };
+
+        guard = Some(Guard {
+            position,
+            direction,
+        });
     }
 
-    Input {
+    let guard =
+        guard.ok_or_else(|| ParseError::missing(input, input, "a guard (`^`, `>`, `v` or `<`)"))?;
+
+    Ok(Input {
         map: Map(map),
-        guard: guard.unwrap(),
-    }
+        guard,
+    })
 }
 
 #[aoc(day6, part1)]
@@ -148,24 +81,14 @@ fn part1(Input { map, guard, .. }: &Input) -> usize {
         let new_position = position + direction.offset();
 
         if map.is_wall(new_position) {
-            direction = direction.turn();
+            direction = direction.turn_right();
             position = position + direction.offset();
         } else {
             position = new_position;
         }
     }
 
-    let mut sum = 0;
-
-    for row in &map.0 {
-        for c in row {
-            if *c == 'X' {
-                sum += 1;
-            }
-        }
-    }
-
-    sum
+    map.0.positions(&'X').count()
 }
 
 #[aoc(day6, part2)]
@@ -186,7 +109,7 @@ fn part2(Input { map, guard, .. }: &Input) -> usize {
     while map.contains(position) {
         let new_position = position + direction.offset();
         if map.is_wall(new_position) {
-            direction = direction.turn();
+            direction = direction.turn_right();
             continue;
         }
 
@@ -203,7 +126,7 @@ fn part2(Input { map, guard, .. }: &Input) -> usize {
             let mut sim_position = position;
 
             // since we know that there is an obstacle, we just turn right
-            let mut sim_direction = direction.turn();
+            let mut sim_direction = direction.turn_right();
 
             while map.contains(sim_position) {
                 let current_visiting = (sim_position, sim_direction);
@@ -217,7 +140,7 @@ fn part2(Input { map, guard, .. }: &Input) -> usize {
                 // check for wall or our obstacle
                 let new_position = sim_position + sim_direction.offset();
                 if map.is_wall(new_position) || new_position == obstacle {
-                    sim_direction = sim_direction.turn();
+                    sim_direction = sim_direction.turn_right();
                     continue;
                 }
 
@@ -231,9 +154,31 @@ fn part2(Input { map, guard, .. }: &Input) -> usize {
     sum
 }
 
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u32 = 6;
+
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        generator(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::day_06::{Coord, Direction, Guard};
+    use crate::day_06::{Coord, Direction4, Guard};
 
     use super::{generator, part1, part2};
 
@@ -250,20 +195,20 @@ mod tests {
 
     #[test]
     fn test_generator() {
-        let gen = generator(INPUT);
+        let gen = generator(INPUT).unwrap();
 
         assert_eq!(
             gen.guard,
             Guard {
                 position: Coord(4, 6),
-                direction: Direction::Up
+                direction: Direction4::Up
             }
         )
     }
 
     #[test]
     fn test_part1() {
-        let gen = generator(INPUT);
+        let gen = generator(INPUT).unwrap();
 
         let res = part1(&gen);
 
@@ -272,7 +217,7 @@ mod tests {
 
     #[test]
     fn test_part2() {
-        let gen = generator(INPUT);
+        let gen = generator(INPUT).unwrap();
 
         let res = part2(&gen);
 
@@ -324,7 +269,7 @@ mod tests {
 
     #[test]
     fn test_other() {
-        let gen = generator(OTHER);
+        let gen = generator(OTHER).unwrap();
 
         let res = part1(&gen);
         assert_eq!(res, 9);
@@ -1,7 +1,12 @@
 use aoc_runner_derive::{aoc, aoc_generator};
 
+use crate::{
+    solution::Solution,
+    utils::{parse::parse_token, ParseError},
+};
+
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct Input {
+pub struct Input {
     equations: Vec<Equation>,
 }
 
@@ -12,21 +17,28 @@ struct Equation {
 }
 
 #[aoc_generator(day7)]
-fn generator(input: &str) -> Input {
+fn generator(input: &str) -> Result<Input, ParseError> {
     let equations = input
         .lines()
         .map(|line| {
-            let parts = line.split(": ").collect::<Vec<_>>();
-            let result = parts[0].parse::<u64>().unwrap();
-            let operands = parts[1]
+            let (result, operands) = line
+                .split_once(": ")
+                .ok_or_else(|| ParseError::missing(input, line, "`: ` after the result"))?;
+            let result = parse_token(input, result, "a result")?;
+            let operands = operands
                 .split_whitespace()
-                .map(|op| op.parse::<u64>().unwrap())
-                .collect();
-            Equation { result, operands }
+                .map(|op| parse_token(input, op, "an operand"))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            if operands.is_empty() {
+                return Err(ParseError::missing(input, line, "an operand"));
+            }
+
+            Ok(Equation { result, operands })
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
 
-    Input { equations }
+    Ok(Input { equations })
 }
 
 fn backtrack(
@@ -36,7 +48,7 @@ fn backtrack(
     funs: &Vec<&dyn Fn(u64, u64) -> u64>,
 ) -> u64 {
     if others.is_empty() {
-        return current.expect("oopsie daisy");
+        return current.expect("equations have at least one operand");
     }
 
     let rest = if others.len() == 1 { &[] } else { &others[1..] };
@@ -107,9 +119,34 @@ fn part2(Input { equations }: &Input) -> u64 {
     sum
 }
 
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u32 = 7;
+
+    type Input = Input;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        generator(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::day_07::{Equation, Input};
+    use crate::{
+        day_07::{Equation, Input},
+        utils::ParseError,
+    };
 
     use super::{generator, part1, part2};
 
@@ -125,7 +162,7 @@ mod tests {
 
     #[test]
     fn test_generator() {
-        let gen = generator(INPUT);
+        let gen = generator(INPUT).unwrap();
 
         assert_eq!(
             gen,
@@ -174,7 +211,7 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        let gen = generator(INPUT);
+        let gen = generator(INPUT).unwrap();
 
         let res = part1(&gen);
 
@@ -183,10 +220,31 @@ mod tests {
 
     #[test]
     fn test_part2() {
-        let gen = generator(INPUT);
+        let gen = generator(INPUT).unwrap();
 
         let res = part2(&gen);
 
         assert_eq!(res, 11387);
     }
+
+    #[test]
+    fn test_generator_malformed() {
+        assert_eq!(
+            generator("190: 10 19\n3267 81 40 27"),
+            Err(ParseError::MissingToken {
+                line: 2,
+                column: 14,
+                expected: "`: ` after the result"
+            })
+        );
+        assert_eq!(
+            generator("190: 10 1a9"),
+            Err(ParseError::InvalidToken {
+                line: 1,
+                column: 9,
+                token: "1a9".into(),
+                expected: "an operand"
+            })
+        );
+    }
 }
@@ -2,61 +2,44 @@ use std::collections::{HashMap, HashSet};
 
 use aoc_runner_derive::{aoc, aoc_generator};
 
-use crate::utils::Coord;
+use crate::{
+    solution::Solution,
+    utils::{Coord, Grid, ParseError},
+};
 
 type Antennas = HashMap<char, Vec<Coord>>;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct Input {
+pub struct Input {
     antennas: Antennas,
-    dim: (i64, i64),
+    map: Grid<char>,
 }
 
 #[aoc_generator(day8)]
-fn generator(input: &str) -> Input {
-    let mut antennas = Antennas::new();
-
-    let mut height = 0;
-    let mut width = 0;
-
-    for (y, line) in input.lines().enumerate() {
-        // very inefficient way to calculate width and height
-        // do not perform this at home!
-        height = y + 1;
-        let mut inner_width = 0;
-        for (x, c) in line.chars().enumerate() {
-            inner_width = x + 1;
-            if c == '.' {
-                continue;
-            }
+fn generator(input: &str) -> Result<Input, ParseError> {
+    let map = Grid::from_str(input, |c| c)?;
 
-            let antenna = Coord(x as i64, y as i64);
+    let mut antennas = Antennas::new();
 
-            let mut others = antennas.get(&c).cloned().unwrap_or_default();
-            others.push(antenna);
-            antennas.insert(c, others);
+    for (antenna, c) in map.iter() {
+        if *c == '.' {
+            continue;
         }
-        width = inner_width;
-    }
 
-    Input {
-        antennas,
-        dim: (width as i64, height as i64),
+        antennas.entry(*c).or_default().push(antenna);
     }
+
+    Ok(Input { antennas, map })
 }
 
-fn get_single_antinode(a: Coord, b: Coord, (width, height): (i64, i64)) -> Option<Coord> {
-    let antinode @ Coord(x, y) = a + (a - b);
+fn get_single_antinode(a: Coord, b: Coord, map: &Grid<char>) -> Option<Coord> {
+    let antinode = a + (a - b);
 
-    if x >= 0 && x < width && y >= 0 && y < height {
-        Some(antinode)
-    } else {
-        None
-    }
+    map.contains(antinode).then_some(antinode)
 }
 
 #[aoc(day8, part1)]
-fn part1(Input { antennas, dim }: &Input) -> usize {
+fn part1(Input { antennas, map }: &Input) -> usize {
     let mut antinodes = HashSet::<Coord>::new();
 
     for antennas in antennas.values() {
@@ -66,11 +49,11 @@ fn part1(Input { antennas, dim }: &Input) -> usize {
                     continue;
                 }
 
-                if let Some(antinode) = get_single_antinode(*a, *b, *dim) {
+                if let Some(antinode) = get_single_antinode(*a, *b, map) {
                     antinodes.insert(antinode);
                 }
 
-                if let Some(antinode) = get_single_antinode(*b, *a, *dim) {
+                if let Some(antinode) = get_single_antinode(*b, *a, map) {
                     antinodes.insert(antinode);
                 }
             }
@@ -80,13 +63,13 @@ fn part1(Input { antennas, dim }: &Input) -> usize {
     antinodes.len()
 }
 
-fn get_all_antinodes(a: Coord, b: Coord, dim: (i64, i64)) -> Vec<Coord> {
+fn get_all_antinodes(a: Coord, b: Coord, map: &Grid<char>) -> Vec<Coord> {
     let diff = a - b;
 
     let mut antinodes = vec![];
 
     let mut n = 1;
-    while let Some(antinode) = get_single_antinode(a, b + (diff * n), dim) {
+    while let Some(antinode) = get_single_antinode(a, b + (diff * n), map) {
         antinodes.push(antinode);
         n += 1;
     }
@@ -95,7 +78,7 @@ fn get_all_antinodes(a: Coord, b: Coord, dim: (i64, i64)) -> Vec<Coord> {
 }
 
 #[aoc(day8, part2)]
-fn part2(Input { antennas, dim }: &Input) -> usize {
+fn part2(Input { antennas, map }: &Input) -> usize {
     let mut antinodes = HashSet::<Coord>::new();
 
     for antennas in antennas.values() {
@@ -105,11 +88,11 @@ fn part2(Input { antennas, dim }: &Input) -> usize {
                     continue;
                 }
 
-                for antinode in &get_all_antinodes(*a, *b, *dim) {
+                for antinode in &get_all_antinodes(*a, *b, map) {
                     antinodes.insert(*antinode);
                 }
 
-                for antinode in &get_all_antinodes(*b, *a, *dim) {
+                for antinode in &get_all_antinodes(*b, *a, map) {
                     antinodes.insert(*antinode);
                 }
             }
@@ -119,11 +102,33 @@ fn part2(Input { antennas, dim }: &Input) -> usize {
     antinodes.len()
 }
 
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u32 = 8;
+
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        generator(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::day_08::part2;
 
-    use super::{generator, part1, Antennas, Coord, Input};
+    use super::{generator, part1, Antennas, Coord};
 
     const INPUT: &str = "............
 ........0...
@@ -140,7 +145,7 @@ mod tests {
 
     #[test]
     fn test_generator() {
-        let gen = generator(INPUT);
+        let gen = generator(INPUT).unwrap();
 
         let mut antennas = Antennas::new();
         let zero_labelled = vec![Coord(8, 1), Coord(5, 2), Coord(7, 3), Coord(4, 4)];
@@ -149,18 +154,13 @@ mod tests {
         let a_labelled = vec![Coord(6, 5), Coord(8, 8), Coord(9, 9)];
         antennas.insert('A', a_labelled);
 
-        assert_eq!(
-            gen,
-            Input {
-                antennas,
-                dim: (12, 12)
-            }
-        );
+        assert_eq!(gen.antennas, antennas);
+        assert_eq!((gen.map.width(), gen.map.height()), (12, 12));
     }
 
     #[test]
     fn test_part1() {
-        let gen = generator(INPUT);
+        let gen = generator(INPUT).unwrap();
 
         let res = part1(&gen);
 
@@ -169,7 +169,7 @@ mod tests {
 
     #[test]
     fn test_part2() {
-        let gen = generator(INPUT);
+        let gen = generator(INPUT).unwrap();
 
         let res = part2(&gen);
 
@@ -1,5 +1,7 @@
 use aoc_runner_derive::{aoc, aoc_generator};
 
+use crate::{solution::Solution, utils::ParseError};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum Mem {
     E,
@@ -7,27 +9,36 @@ enum Mem {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct Input {
+pub struct Input {
     mem: Vec<Mem>,
 }
 
 #[aoc_generator(day9)]
-fn generator(input: &str) -> Input {
-    let mem = input
-        .chars()
-        .enumerate()
-        .flat_map(|(n, c)| {
-            let num = c.to_digit(10).unwrap() as usize;
-
-            if n % 2 == 1 {
-                vec![Mem::E; num]
-            } else {
-                vec![Mem::S(n / 2, num); num]
-            }
-        })
-        .collect();
+fn generator(input: &str) -> Result<Input, ParseError> {
+    if input.is_empty() {
+        return Err(ParseError::missing(input, input, "a digit"));
+    }
+
+    let mut mem = vec![];
 
-    Input { mem }
+    for (n, (i, c)) in input.char_indices().enumerate() {
+        let Some(num) = c.to_digit(10) else {
+            return Err(ParseError::invalid(
+                input,
+                &input[i..i + c.len_utf8()],
+                "a digit",
+            ));
+        };
+        let num = num as usize;
+
+        if n % 2 == 1 {
+            mem.extend(vec![Mem::E; num]);
+        } else {
+            mem.extend(vec![Mem::S(n / 2, num); num]);
+        }
+    }
+
+    Ok(Input { mem })
 }
 
 #[aoc(day9, part1)]
@@ -71,6 +82,7 @@ fn part1(Input { mem }: &Input) -> usize {
     sum
 }
 
+#[allow(clippy::needless_range_loop)]
 fn find_empty_space_with_size(mem: &[Mem], size: usize, max: usize) -> Option<usize> {
     for i in 0..max {
         let mut found = true;
@@ -145,15 +157,40 @@ fn part2(Input { mem }: &Input) -> usize {
     sum
 }
 
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u32 = 9;
+
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        generator(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::day_09::{part2, Input, Mem};
+    use crate::{
+        day_09::{part2, Input, Mem},
+        utils::ParseError,
+    };
 
     use super::{generator, part1};
 
     #[test]
     fn test_generator() {
-        let gen = generator("12345");
+        let gen = generator("12345").unwrap();
 
         assert_eq!(
             gen,
@@ -183,7 +220,7 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        let gen = generator(INPUT);
+        let gen = generator(INPUT).unwrap();
 
         let res = part1(&gen);
 
@@ -192,10 +229,31 @@ mod tests {
 
     #[test]
     fn test_part2() {
-        let gen = generator(INPUT);
+        let gen = generator(INPUT).unwrap();
 
         let res = part2(&gen);
 
         assert_eq!(res, 2858);
     }
+
+    #[test]
+    fn test_generator_malformed() {
+        assert_eq!(
+            generator("12x45"),
+            Err(ParseError::InvalidToken {
+                line: 1,
+                column: 3,
+                token: "x".into(),
+                expected: "a digit"
+            })
+        );
+        assert_eq!(
+            generator(""),
+            Err(ParseError::MissingToken {
+                line: 1,
+                column: 1,
+                expected: "a digit"
+            })
+        );
+    }
 }
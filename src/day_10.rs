@@ -1,89 +1,34 @@
-use std::collections::HashSet;
-
 use aoc_runner_derive::{aoc, aoc_generator};
 
-use crate::utils::Coord;
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct Trails {
-    map: Vec<Vec<u32>>,
-}
-
-impl Trails {
-    fn at(&self, Coord(x, y): Coord) -> Option<u32> {
-        self.map
-            .get(y as usize)
-            .and_then(|line| line.get(x as usize))
-            .copied()
-    }
-}
+use crate::{
+    solution::Solution,
+    utils::{
+        search::{bfs, count_paths},
+        Coord, Grid, ParseError,
+    },
+};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-struct Input {
-    map: Trails,
+pub struct Input {
+    map: Grid<u32>,
     starts: Vec<Coord>,
 }
 
 #[aoc_generator(day10)]
-fn generator(input: &str) -> Input {
-    let map: Vec<Vec<u32>> = input
-        .lines()
-        .map(|line| line.chars().map(|c| c.to_digit(10).unwrap()).collect())
-        .collect();
-
-    let mut starts = vec![];
-
-    for (y, line) in map.iter().enumerate() {
-        for (x, c) in line.iter().enumerate() {
-            if *c == 0 {
-                starts.push(Coord(x as i64, y as i64));
-            }
-        }
-    }
+fn generator(input: &str) -> Result<Input, ParseError> {
+    let map = Grid::try_from_str(input, "a height", |c| c.to_digit(10))?;
+    let starts = map.positions(&0).collect();
 
-    Input {
-        map: Trails { map },
-        starts,
-    }
+    Ok(Input { map, starts })
 }
 
-fn backtrack(current: Coord, map: &Trails, ends: &mut HashSet<Coord>) -> usize {
-    let Some(num) = map.at(current) else {
-        unreachable!("This should not happen...");
-    };
-
-    let mut sum = 0;
-
-    if num == 9 {
-        ends.insert(current);
-        return 1;
-    }
-
-    if let Some(up) = map.at(current.up()) {
-        if up == num + 1 {
-            sum += backtrack(current.up(), map, ends);
-        }
-    };
-
-    if let Some(right) = map.at(current.right()) {
-        if right == num + 1 {
-            sum += backtrack(current.right(), map, ends);
-        }
-    };
-
-    if let Some(down) = map.at(current.down()) {
-        if down == num + 1 {
-            sum += backtrack(current.down(), map, ends);
-        }
-    };
-
-    if let Some(left) = map.at(current.left()) {
-        if left == num + 1 {
-            sum += backtrack(current.left(), map, ends);
-        }
-    };
+/// All positions that are exactly one higher than `current`.
+fn climb(current: Coord, map: &Grid<u32>) -> impl Iterator<Item = Coord> + '_ {
+    let height = map[current];
 
-    sum
+    current
+        .neighbors4()
+        .filter(move |next| map.get(*next) == Some(&(height + 1)))
 }
 
 #[aoc(day10, part1)]
@@ -91,11 +36,13 @@ fn part1(Input { map, starts }: &Input) -> usize {
     let mut sum = 0;
 
     for start in starts {
-        let mut ends = HashSet::new();
-
-        backtrack(*start, map, &mut ends);
+        let reachable = bfs(*start, |current| climb(*current, map));
 
-        sum += ends.len();
+        sum += reachable
+            .order()
+            .iter()
+            .filter(|position| map[**position] == 9)
+            .count();
     }
 
     sum
@@ -106,14 +53,38 @@ fn part2(Input { map, starts }: &Input) -> usize {
     let mut sum = 0;
 
     for start in starts {
-        let mut ends = HashSet::new();
-
-        sum += backtrack(*start, map, &mut ends);
+        sum += count_paths(
+            *start,
+            |current| climb(*current, map),
+            |current| map[*current] == 9,
+        );
     }
 
     sum
 }
 
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u32 = 10;
+
+    type Input = Input;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        generator(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{day_10::part2, utils::Coord};
@@ -130,7 +101,7 @@ mod tests {
 
     #[test]
     fn test_generator() {
-        let gen = generator(SIMPLE_INPUT);
+        let gen = generator(SIMPLE_INPUT).unwrap();
 
         assert_eq!(gen.starts, vec![Coord(3, 0)]);
         assert_eq!(gen.starts.len(), 1);
@@ -138,7 +109,7 @@ mod tests {
 
     #[test]
     fn test_simple_input_part1() {
-        let gen = generator(SIMPLE_INPUT);
+        let gen = generator(SIMPLE_INPUT).unwrap();
 
         let res = part1(&gen);
 
@@ -156,7 +127,7 @@ mod tests {
 
     #[test]
     fn test_complext_input_part1() {
-        let gen = generator(COMPLEX_INPUT);
+        let gen = generator(COMPLEX_INPUT).unwrap();
 
         let res = part1(&gen);
 
@@ -165,7 +136,7 @@ mod tests {
 
     #[test]
     fn test_complext_input_part2() {
-        let gen = generator(COMPLEX_INPUT);
+        let gen = generator(COMPLEX_INPUT).unwrap();
 
         let res = part2(&gen);
 
@@ -2,6 +2,11 @@ use std::collections::HashMap;
 
 use aoc_runner_derive::{aoc, aoc_generator};
 
+use crate::{
+    solution::Solution,
+    utils::{parse::parse_token, ParseError},
+};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Stone(u64);
 
@@ -24,18 +29,18 @@ impl Stone {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-struct Input {
+pub struct Input {
     stones: Vec<Stone>,
 }
 
 #[aoc_generator(day11)]
-fn generator(input: &str) -> Input {
+fn generator(input: &str) -> Result<Input, ParseError> {
     let stones = input
         .split_whitespace()
-        .map(|s| Stone(s.parse().unwrap()))
-        .collect();
+        .map(|s| parse_token(input, s, "an engraved number").map(Stone))
+        .collect::<Result<_, _>>()?;
 
-    Input { stones }
+    Ok(Input { stones })
 }
 
 fn perform_blinking(stones: &Vec<Stone>, iterations: usize) -> u64 {
@@ -76,6 +81,28 @@ fn part2(Input { stones }: &Input) -> u64 {
     perform_blinking(stones, 75)
 }
 
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u32 = 11;
+
+    type Input = Input;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError> {
+        generator(input)
+    }
+
+    fn part1(input: &Self::Input) -> Self::Answer1 {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Self::Answer2 {
+        part2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::day_11::{part1, Input, Stone};
@@ -86,7 +113,7 @@ mod tests {
 
     #[test]
     fn test_generator() {
-        let gen = generator(INPUT);
+        let gen = generator(INPUT).unwrap();
 
         assert_eq!(
             gen,
@@ -106,7 +133,7 @@ mod tests {
 
     #[test]
     fn test_part1() {
-        let gen = generator("125 17");
+        let gen = generator("125 17").unwrap();
 
         let res = part1(&gen);
 
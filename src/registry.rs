@@ -0,0 +1,76 @@
+use std::{fs, io, path::PathBuf};
+
+use crate::{
+    day_01::Day01, day_02::Day02, day_03::Day03, day_04::Day04, day_05::Day05, day_06::Day06,
+    day_07::Day07, day_08::Day08, day_09::Day09, day_10::Day10, day_11::Day11, solution::Solution,
+    utils::ParseError,
+};
+
+/// Object safe view of a [`Solution`], so that all days can be stored in one list.
+///
+/// Both parts parse the input on their own and return the displayed answer.
+pub trait Runnable: Sync {
+    fn day(&self) -> u32;
+
+    fn part1(&self, input: &str) -> Result<String, ParseError>;
+
+    fn part2(&self, input: &str) -> Result<String, ParseError>;
+}
+
+impl<S: Solution + Sync> Runnable for S {
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+
+    fn part1(&self, input: &str) -> Result<String, ParseError> {
+        S::parse(input).map(|input| S::part1(&input).to_string())
+    }
+
+    fn part2(&self, input: &str) -> Result<String, ParseError> {
+        S::parse(input).map(|input| S::part2(&input).to_string())
+    }
+}
+
+/// All implemented days in ascending order.
+pub static DAYS: &[&dyn Runnable] = &[
+    &Day01, &Day02, &Day03, &Day04, &Day05, &Day06, &Day07, &Day08, &Day09, &Day10, &Day11,
+];
+
+/// Path of the puzzle input for `day`, relative to the repository root.
+pub fn input_path(day: u32) -> PathBuf {
+    PathBuf::from(format!("input/2024/day{day}.txt"))
+}
+
+/// Read the puzzle input for `day` without trailing newlines, just like cargo-aoc passes it to the
+/// generators.
+pub fn read_input(day: u32) -> io::Result<String> {
+    let input = fs::read_to_string(input_path(day))?;
+
+    Ok(input.trim_end_matches('\n').to_string())
+}
+
+pub fn find(day: u32) -> Option<&'static dyn Runnable> {
+    DAYS.iter().find(|d| d.day() == day).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find, DAYS};
+
+    #[test]
+    fn test_days_are_ordered() {
+        let days = DAYS.iter().map(|d| d.day()).collect::<Vec<_>>();
+
+        assert_eq!(days, (1..=11).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_find() {
+        let day = find(1).unwrap();
+
+        assert_eq!(day.part1("3   4\n4   3"), Ok("0".into()));
+        assert_eq!(day.part2("3   4\n4   3"), Ok("7".into()));
+        assert!(day.part1("3   x").is_err());
+        assert!(find(26).is_none());
+    }
+}
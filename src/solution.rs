@@ -0,0 +1,18 @@
+use std::fmt::Display;
+
+use crate::utils::ParseError;
+
+/// A single day of the calendar: the generator and both parts of the puzzle.
+pub trait Solution {
+    const DAY: u32;
+
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+
+    fn part1(input: &Self::Input) -> Self::Answer1;
+
+    fn part2(input: &Self::Input) -> Self::Answer2;
+}
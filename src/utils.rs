@@ -1,4 +1,13 @@
-use std::ops::{Add, Mul, Sub};
+use std::ops::{Add, Index, IndexMut, Mul, Sub};
+
+pub mod ansi;
+pub mod direction;
+pub mod lcg;
+pub mod parse;
+pub mod search;
+
+pub use direction::{Direction4, Direction8};
+pub use parse::ParseError;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Coord(pub i64, pub i64);
@@ -48,4 +57,329 @@ impl Coord {
     pub fn left(&self) -> Self {
         *self + Self::LEFT
     }
+
+    /// The orthogonal neighbours in clockwise order, starting with the one above.
+    pub fn neighbors4(&self) -> impl Iterator<Item = Coord> {
+        let current = *self;
+        Direction4::all().map(move |direction| current + direction.offset())
+    }
+
+    /// The orthogonal and diagonal neighbours in clockwise order, starting with the one above.
+    pub fn neighbors8(&self) -> impl Iterator<Item = Coord> {
+        let current = *self;
+        Direction8::all().map(move |direction| current + direction.offset())
+    }
+
+    /// Like [`Coord::neighbors4`], but only yields neighbours within a grid of dimension `dim`.
+    pub fn neighbors4_within(&self, dim: (usize, usize)) -> impl Iterator<Item = Coord> {
+        self.neighbors4().filter(move |n| n.is_within(dim))
+    }
+
+    /// Like [`Coord::neighbors8`], but only yields neighbours within a grid of dimension `dim`.
+    pub fn neighbors8_within(&self, dim: (usize, usize)) -> impl Iterator<Item = Coord> {
+        self.neighbors8().filter(move |n| n.is_within(dim))
+    }
+
+    /// Whether this coordinate lies within a grid of dimension `(width, height)`.
+    pub fn is_within(&self, (width, height): (usize, usize)) -> bool {
+        let Coord(x, y) = *self;
+        x >= 0 && y >= 0 && (x as usize) < width && (y as usize) < height
+    }
+
+    pub fn manhattan(&self, other: Coord) -> u64 {
+        self.0.abs_diff(other.0) + self.1.abs_diff(other.1)
+    }
+
+    pub fn chebyshev(&self, other: Coord) -> u64 {
+        self.0.abs_diff(other.0).max(self.1.abs_diff(other.1))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(cells: Vec<T>, width: usize) -> Self {
+        let height = cells.len().checked_div(width).unwrap_or_default();
+        assert_eq!(
+            width * height,
+            cells.len(),
+            "grid cells do not fill a {width} wide grid"
+        );
+
+        Grid {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// Parse a grid from lines of characters, mapping each character with `f`.
+    pub fn from_str(input: &str, mut f: impl FnMut(char) -> T) -> Result<Self, ParseError> {
+        Self::try_from_str(input, "any character", |c| Some(f(c)))
+    }
+
+    /// Parse a grid from lines of characters, rejecting every character for which `f` returns
+    /// `None` as not being `expected`.
+    pub fn try_from_str(
+        input: &str,
+        expected: &'static str,
+        mut f: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut cells = vec![];
+        let mut width = 0;
+
+        for (y, line) in input.lines().enumerate() {
+            let mut line_width = 0;
+
+            for (x, c) in line.chars().enumerate() {
+                if y > 0 && x >= width {
+                    return Err(ParseError::InvalidToken {
+                        line: y + 1,
+                        column: x + 1,
+                        token: c.to_string(),
+                        expected: "end of line",
+                    });
+                }
+
+                let Some(cell) = f(c) else {
+                    return Err(ParseError::InvalidToken {
+                        line: y + 1,
+                        column: x + 1,
+                        token: c.to_string(),
+                        expected,
+                    });
+                };
+
+                cells.push(cell);
+                line_width += 1;
+            }
+
+            if y == 0 {
+                width = line_width;
+            } else if line_width < width {
+                return Err(ParseError::MissingToken {
+                    line: y + 1,
+                    column: line_width + 1,
+                    expected,
+                });
+            }
+        }
+
+        Ok(Grid::new(cells, width))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn dim(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    pub fn contains(&self, coord: Coord) -> bool {
+        coord.is_within(self.dim())
+    }
+
+    fn index(&self, coord: Coord) -> Option<usize> {
+        if !self.contains(coord) {
+            return None;
+        }
+
+        let Coord(x, y) = coord;
+        Some(y as usize * self.width + x as usize)
+    }
+
+    pub fn get(&self, coord: Coord) -> Option<&T> {
+        self.index(coord).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, coord: Coord) -> Option<&mut T> {
+        self.index(coord).map(|i| &mut self.cells[i])
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let len = if x < self.width { self.height } else { 0 };
+
+        self.cells
+            .iter()
+            .skip(x)
+            .step_by(self.width.max(1))
+            .take(len)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Iterate over all coordinates of the grid in row-major order.
+    pub fn coords(&self) -> impl Iterator<Item = Coord> {
+        let width = self.width;
+
+        (0..self.cells.len()).map(move |i| Coord((i % width) as i64, (i / width) as i64))
+    }
+
+    /// Iterate over all cells of the grid (together with their coordinate) in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Coord, &T)> {
+        self.coords().zip(self.cells.iter())
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    pub fn find(&self, value: &T) -> Option<Coord> {
+        self.positions(value).next()
+    }
+
+    pub fn positions<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Coord> + 'a {
+        self.iter()
+            .filter(move |(_, cell)| *cell == value)
+            .map(|(coord, _)| coord)
+    }
+}
+
+impl<T> Index<Coord> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, coord: Coord) -> &Self::Output {
+        self.get(coord)
+            .unwrap_or_else(|| panic!("{coord:?} is out of bounds"))
+    }
+}
+
+impl<T> IndexMut<Coord> for Grid<T> {
+    fn index_mut(&mut self, coord: Coord) -> &mut Self::Output {
+        self.get_mut(coord)
+            .unwrap_or_else(|| panic!("{coord:?} is out of bounds"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Coord, Grid, ParseError};
+
+    #[test]
+    fn test_neighbors() {
+        let coord = Coord(0, 1);
+
+        assert_eq!(
+            coord.neighbors4().collect::<Vec<_>>(),
+            vec![Coord(0, 0), Coord(1, 1), Coord(0, 2), Coord(-1, 1)]
+        );
+        assert_eq!(coord.neighbors8().count(), 8);
+        assert_eq!(
+            coord.neighbors4_within((2, 2)).collect::<Vec<_>>(),
+            vec![Coord(0, 0), Coord(1, 1)]
+        );
+        assert_eq!(
+            coord.neighbors8_within((2, 2)).collect::<Vec<_>>(),
+            vec![Coord(0, 0), Coord(1, 0), Coord(1, 1)]
+        );
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Coord(1, -2);
+        let b = Coord(-3, 4);
+
+        assert_eq!(a.manhattan(b), 10);
+        assert_eq!(b.manhattan(a), 10);
+        assert_eq!(a.chebyshev(b), 6);
+        assert_eq!(a.manhattan(a), 0);
+    }
+
+    const INPUT: &str = "abc
+def";
+
+    #[test]
+    fn test_grid_from_str() {
+        let grid = Grid::from_str(INPUT, |c| c).unwrap();
+
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid.get(Coord(1, 1)), Some(&'e'));
+        assert_eq!(grid.get(Coord(3, 0)), None);
+        assert_eq!(grid.get(Coord(-1, 0)), None);
+        assert_eq!(grid[Coord(2, 0)], 'c');
+    }
+
+    #[test]
+    fn test_grid_rows_and_columns() {
+        let grid = Grid::from_str(INPUT, |c| c).unwrap();
+
+        let rows = grid.rows().map(|row| row.to_vec()).collect::<Vec<_>>();
+        assert_eq!(rows, vec![vec!['a', 'b', 'c'], vec!['d', 'e', 'f']]);
+
+        let columns = grid
+            .columns()
+            .map(|column| column.copied().collect::<String>())
+            .collect::<Vec<_>>();
+        assert_eq!(columns, vec!["ad", "be", "cf"]);
+
+        assert_eq!(grid.row(1), Some(&['d', 'e', 'f'][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(grid.column(3).count(), 0);
+    }
+
+    #[test]
+    fn test_grid_find_and_mutate() {
+        let mut grid = Grid::try_from_str("0110\n1001", "a digit", |c| c.to_digit(10)).unwrap();
+
+        assert_eq!(grid.find(&1), Some(Coord(1, 0)));
+        assert_eq!(
+            grid.positions(&0).collect::<Vec<_>>(),
+            vec![Coord(0, 0), Coord(3, 0), Coord(1, 1), Coord(2, 1)]
+        );
+
+        *grid.get_mut(Coord(0, 0)).unwrap() = 1;
+        grid[Coord(3, 1)] = 0;
+
+        assert_eq!(grid.find(&0), Some(Coord(3, 0)));
+        assert_eq!(grid[Coord(3, 1)], 0);
+    }
+
+    #[test]
+    fn test_grid_parse_errors() {
+        assert_eq!(
+            Grid::try_from_str("01\n2x", "a digit", |c| c.to_digit(10)),
+            Err(ParseError::InvalidToken {
+                line: 2,
+                column: 2,
+                token: "x".into(),
+                expected: "a digit"
+            })
+        );
+        assert_eq!(
+            Grid::from_str("ab\na", |c| c),
+            Err(ParseError::MissingToken {
+                line: 2,
+                column: 2,
+                expected: "any character"
+            })
+        );
+        assert_eq!(
+            Grid::from_str("ab\nabc", |c| c),
+            Err(ParseError::InvalidToken {
+                line: 2,
+                column: 3,
+                token: "c".into(),
+                expected: "end of line"
+            })
+        );
+    }
 }
@@ -0,0 +1,34 @@
+//! Minimal ANSI escape codes for colored terminal output.
+
+/// An SGR color or style code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Color(pub &'static str);
+
+impl Color {
+    pub const DIM: Color = Color("2");
+    pub const RED: Color = Color("31");
+    pub const GREEN: Color = Color("32");
+    pub const YELLOW: Color = Color("33");
+    pub const BLUE: Color = Color("34");
+    pub const MAGENTA: Color = Color("35");
+    pub const CYAN: Color = Color("36");
+    pub const BRIGHT_RED: Color = Color("91");
+    pub const BRIGHT_BLUE: Color = Color("94");
+    pub const BOLD_WHITE: Color = Color("1;37");
+}
+
+/// Wrap `text` in the escape codes for `color`, resetting all styles afterwards.
+pub fn paint(text: &str, color: Color) -> String {
+    format!("\x1b[{}m{text}\x1b[0m", color.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{paint, Color};
+
+    #[test]
+    fn test_paint() {
+        assert_eq!(paint("XMAS", Color::RED), "\x1b[31mXMAS\x1b[0m");
+        assert_eq!(paint("", Color::BOLD_WHITE), "\x1b[1;37m\x1b[0m");
+    }
+}
@@ -0,0 +1,289 @@
+use std::{error::Error, fmt::Display, str::FromStr};
+
+use super::Coord;
+
+/// One of the four orthogonal directions on a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction4 {
+    /// All directions in clockwise order, starting with [`Direction4::Up`].
+    pub const ALL: [Direction4; 4] = [
+        Direction4::Up,
+        Direction4::Right,
+        Direction4::Down,
+        Direction4::Left,
+    ];
+
+    pub fn all() -> impl Iterator<Item = Direction4> {
+        Self::ALL.into_iter()
+    }
+
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 4]
+    }
+
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 3) % 4]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 2) % 4]
+    }
+
+    pub fn offset(self) -> Coord {
+        match self {
+            Direction4::Up => Coord::UP,
+            Direction4::Right => Coord::RIGHT,
+            Direction4::Down => Coord::DOWN,
+            Direction4::Left => Coord::LEFT,
+        }
+    }
+
+    /// Parse one of `^`, `>`, `v` or `<`.
+    pub fn from_arrow(c: char) -> Option<Self> {
+        match c {
+            '^' => Some(Direction4::Up),
+            '>' => Some(Direction4::Right),
+            'v' => Some(Direction4::Down),
+            '<' => Some(Direction4::Left),
+            _ => None,
+        }
+    }
+
+    /// Parse one of `N`, `E`, `S` or `W`.
+    pub fn from_compass(c: char) -> Option<Self> {
+        match c {
+            'N' => Some(Direction4::Up),
+            'E' => Some(Direction4::Right),
+            'S' => Some(Direction4::Down),
+            'W' => Some(Direction4::Left),
+            _ => None,
+        }
+    }
+
+    /// Parse one of `U`, `R`, `D` or `L`.
+    pub fn from_letter(c: char) -> Option<Self> {
+        match c {
+            'U' => Some(Direction4::Up),
+            'R' => Some(Direction4::Right),
+            'D' => Some(Direction4::Down),
+            'L' => Some(Direction4::Left),
+            _ => None,
+        }
+    }
+
+    /// Parse an arrow, a compass point or a letter.
+    pub fn from_char(c: char) -> Option<Self> {
+        Self::from_arrow(c)
+            .or_else(|| Self::from_compass(c))
+            .or_else(|| Self::from_letter(c))
+    }
+}
+
+impl From<Direction4> for Coord {
+    fn from(direction: Direction4) -> Self {
+        direction.offset()
+    }
+}
+
+impl FromStr for Direction4 {
+    type Err = InvalidDirection;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Self::from_char(c),
+            _ => None,
+        }
+        .ok_or_else(|| InvalidDirection(s.to_string()))
+    }
+}
+
+/// One of the four orthogonal or four diagonal directions on a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    /// All directions in clockwise order, starting with [`Direction8::Up`].
+    pub const ALL: [Direction8; 8] = [
+        Direction8::Up,
+        Direction8::UpRight,
+        Direction8::Right,
+        Direction8::DownRight,
+        Direction8::Down,
+        Direction8::DownLeft,
+        Direction8::Left,
+        Direction8::UpLeft,
+    ];
+
+    pub fn all() -> impl Iterator<Item = Direction8> {
+        Self::ALL.into_iter()
+    }
+
+    /// Rotate clockwise by 45 degrees.
+    pub fn turn_right(self) -> Self {
+        Self::ALL[(self as usize + 1) % 8]
+    }
+
+    /// Rotate counterclockwise by 45 degrees.
+    pub fn turn_left(self) -> Self {
+        Self::ALL[(self as usize + 7) % 8]
+    }
+
+    pub fn reverse(self) -> Self {
+        Self::ALL[(self as usize + 4) % 8]
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self as usize % 2 == 1
+    }
+
+    pub fn offset(self) -> Coord {
+        match self {
+            Direction8::Up => Coord::UP,
+            Direction8::UpRight => Coord::UP + Coord::RIGHT,
+            Direction8::Right => Coord::RIGHT,
+            Direction8::DownRight => Coord::DOWN + Coord::RIGHT,
+            Direction8::Down => Coord::DOWN,
+            Direction8::DownLeft => Coord::DOWN + Coord::LEFT,
+            Direction8::Left => Coord::LEFT,
+            Direction8::UpLeft => Coord::UP + Coord::LEFT,
+        }
+    }
+}
+
+impl From<Direction4> for Direction8 {
+    fn from(direction: Direction4) -> Self {
+        Self::ALL[direction as usize * 2]
+    }
+}
+
+impl From<Direction8> for Coord {
+    fn from(direction: Direction8) -> Self {
+        direction.offset()
+    }
+}
+
+impl FromStr for Direction8 {
+    type Err = InvalidDirection;
+
+    /// Parse a [`Direction4`] or a combination of two of them, e.g. `NE`, `UR` or `^>`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidDirection(s.to_string());
+
+        let mut chars = s.chars();
+        let first = chars.next().ok_or_else(invalid)?;
+
+        let (vertical, horizontal) = match (chars.next(), chars.next()) {
+            (None, _) => {
+                return Direction4::from_char(first)
+                    .map(Direction8::from)
+                    .ok_or_else(invalid)
+            }
+            (Some(second), None) => (first, second),
+            _ => return Err(invalid()),
+        };
+
+        // both characters have to use the same notation
+        let parsers: [fn(char) -> Option<Direction4>; 3] = [
+            Direction4::from_arrow,
+            Direction4::from_compass,
+            Direction4::from_letter,
+        ];
+        let pair = parsers
+            .into_iter()
+            .find_map(|parse| Some((parse(vertical)?, parse(horizontal)?)));
+
+        match pair {
+            Some((Direction4::Up, Direction4::Right)) => Ok(Direction8::UpRight),
+            Some((Direction4::Down, Direction4::Right)) => Ok(Direction8::DownRight),
+            Some((Direction4::Down, Direction4::Left)) => Ok(Direction8::DownLeft),
+            Some((Direction4::Up, Direction4::Left)) => Ok(Direction8::UpLeft),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidDirection(pub String);
+
+impl Display for InvalidDirection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}` is not a direction", self.0)
+    }
+}
+
+impl Error for InvalidDirection {}
+
+#[cfg(test)]
+mod tests {
+    use super::{Coord, Direction4, Direction8, InvalidDirection};
+
+    #[test]
+    fn test_direction4_rotation() {
+        assert_eq!(Direction4::Up.turn_right(), Direction4::Right);
+        assert_eq!(Direction4::Left.turn_right(), Direction4::Up);
+        assert_eq!(Direction4::Up.turn_left(), Direction4::Left);
+        assert_eq!(Direction4::Right.reverse(), Direction4::Left);
+
+        for direction in Direction4::all() {
+            assert_eq!(direction.turn_right().turn_left(), direction);
+            assert_eq!(direction.reverse().offset(), direction.offset() * -1);
+        }
+    }
+
+    #[test]
+    fn test_direction4_parse() {
+        assert_eq!(Direction4::from_arrow('v'), Some(Direction4::Down));
+        assert_eq!(Direction4::from_arrow('D'), None);
+        assert_eq!(Direction4::from_char('W'), Some(Direction4::Left));
+        assert_eq!(Direction4::from_char('U'), Some(Direction4::Up));
+        assert_eq!("R".parse(), Ok(Direction4::Right));
+        assert_eq!(
+            "RR".parse::<Direction4>(),
+            Err(InvalidDirection("RR".into()))
+        );
+    }
+
+    #[test]
+    fn test_direction8() {
+        assert_eq!(Direction8::all().count(), 8);
+        assert_eq!(Direction8::UpLeft.turn_right(), Direction8::Up);
+        assert_eq!(Direction8::Up.turn_left(), Direction8::UpLeft);
+        assert_eq!(Direction8::DownLeft.reverse(), Direction8::UpRight);
+        assert_eq!(Coord::from(Direction8::DownLeft), Coord(-1, 1));
+        assert_eq!(Direction8::from(Direction4::Left), Direction8::Left);
+        assert_eq!(Direction8::all().filter(|d| d.is_diagonal()).count(), 4);
+
+        for direction in Direction8::all() {
+            assert_eq!(direction.reverse().offset(), direction.offset() * -1);
+        }
+    }
+
+    #[test]
+    fn test_direction8_parse() {
+        assert_eq!("NE".parse(), Ok(Direction8::UpRight));
+        assert_eq!("DL".parse(), Ok(Direction8::DownLeft));
+        assert_eq!("^<".parse(), Ok(Direction8::UpLeft));
+        assert_eq!("S".parse(), Ok(Direction8::Down));
+        assert!("EN".parse::<Direction8>().is_err());
+        assert!("NR".parse::<Direction8>().is_err());
+        assert!("".parse::<Direction8>().is_err());
+    }
+}
@@ -0,0 +1,23 @@
+//! A tiny pseudo random number generator for generated test and benchmark inputs.
+
+/// Linear congruential generator with Knuth's MMIX constants. Every seed always yields the same
+/// sequence, so generated inputs are reproducible.
+#[derive(Debug, Clone)]
+pub struct Lcg(u64);
+
+impl Lcg {
+    pub fn new(seed: u64) -> Self {
+        Lcg(seed)
+    }
+
+    /// A number in `0..max`.
+    pub fn below(&mut self, max: u64) -> u64 {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1);
+        (self.0 >> 33) % max
+    }
+
+    /// One of `items`.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+}
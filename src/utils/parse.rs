@@ -0,0 +1,310 @@
+use std::{error::Error, fmt::Display, str::FromStr};
+
+/// Error returned by the generators when the puzzle input is malformed.
+///
+/// Lines and columns are 1-based, so they can be matched directly against an editor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// A token was found, but it does not match what was expected at this position.
+    InvalidToken {
+        line: usize,
+        column: usize,
+        token: String,
+        expected: &'static str,
+    },
+    /// The line (or the whole input) ended before an expected token.
+    MissingToken {
+        line: usize,
+        column: usize,
+        expected: &'static str,
+    },
+}
+
+impl ParseError {
+    /// Create an error for `token`, which has to be a slice of `input`.
+    pub fn invalid(input: &str, token: &str, expected: &'static str) -> Self {
+        let (line, column) = locate(input, offset_of(input, token));
+
+        ParseError::InvalidToken {
+            line,
+            column,
+            token: token.to_string(),
+            expected,
+        }
+    }
+
+    /// Create an error pointing right behind `after`, which has to be a slice of `input`.
+    pub fn missing(input: &str, after: &str, expected: &'static str) -> Self {
+        let (line, column) = locate(input, offset_of(input, after) + after.len());
+
+        ParseError::MissingToken {
+            line,
+            column,
+            expected,
+        }
+    }
+
+    /// Move the error `lines` lines down, for inputs that are parsed line by line.
+    pub fn offset_lines(mut self, lines: usize) -> Self {
+        match &mut self {
+            ParseError::InvalidToken { line, .. } | ParseError::MissingToken { line, .. } => {
+                *line += lines
+            }
+        }
+
+        self
+    }
+
+    pub fn line(&self) -> usize {
+        match self {
+            ParseError::InvalidToken { line, .. } | ParseError::MissingToken { line, .. } => *line,
+        }
+    }
+
+    pub fn column(&self) -> usize {
+        match self {
+            ParseError::InvalidToken { column, .. } | ParseError::MissingToken { column, .. } => {
+                *column
+            }
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::InvalidToken {
+                line,
+                column,
+                token,
+                expected,
+            } => write!(
+                f,
+                "line {line}, column {column}: expected {expected}, found `{token}`"
+            ),
+            ParseError::MissingToken {
+                line,
+                column,
+                expected,
+            } => write!(
+                f,
+                "line {line}, column {column}: expected {expected}, found nothing"
+            ),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parse `token` (a slice of `input`), reporting its position in `input` on failure.
+pub fn parse_token<T: FromStr>(
+    input: &str,
+    token: &str,
+    expected: &'static str,
+) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::invalid(input, token, expected))
+}
+
+/// Parse every line of `input` into whitespace separated values, allowing any number per line.
+pub fn parse_rows<T: FromStr>(
+    input: &str,
+    expected: &'static str,
+) -> Result<Vec<Vec<T>>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            line.split_whitespace()
+                .map(|token| parse_token(input, token, expected))
+                .collect()
+        })
+        .collect()
+}
+
+/// Parse every line of `input` into exactly `N` whitespace separated values.
+pub fn parse_table<T: FromStr, const N: usize>(
+    input: &str,
+    expected: &'static str,
+) -> Result<Vec<[T; N]>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            Ok(parse_line(input, line, N, expected)?
+                .try_into()
+                .unwrap_or_else(|_| unreachable!("exactly {N} values were parsed")))
+        })
+        .collect()
+}
+
+/// Parse every line of `input` into exactly `width` whitespace separated values and return them
+/// column by column.
+pub fn parse_columns<T: FromStr>(
+    input: &str,
+    width: usize,
+    expected: &'static str,
+) -> Result<Vec<Vec<T>>, ParseError> {
+    let mut columns = (0..width).map(|_| vec![]).collect::<Vec<_>>();
+
+    for line in input.lines() {
+        let row = parse_line(input, line, width, expected)?;
+
+        for (column, value) in columns.iter_mut().zip(row) {
+            column.push(value);
+        }
+    }
+
+    Ok(columns)
+}
+
+/// Parse `line` (a slice of `input`) into exactly `width` whitespace separated values.
+fn parse_line<T: FromStr>(
+    input: &str,
+    line: &str,
+    width: usize,
+    expected: &'static str,
+) -> Result<Vec<T>, ParseError> {
+    let mut tokens = line.split_whitespace();
+    let mut row = Vec::with_capacity(width);
+
+    for _ in 0..width {
+        let token = tokens
+            .next()
+            .ok_or_else(|| ParseError::missing(input, line, expected))?;
+        row.push(parse_token(input, token, expected)?);
+    }
+
+    if let Some(token) = tokens.next() {
+        return Err(ParseError::invalid(input, token, "end of line"));
+    }
+
+    Ok(row)
+}
+
+fn offset_of(input: &str, part: &str) -> usize {
+    let offset = (part.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+    assert!(
+        offset <= input.len(),
+        "`{part}` is not a part of the parsed input"
+    );
+
+    offset
+}
+
+fn locate(input: &str, offset: usize) -> (usize, usize) {
+    let before = &input[..offset];
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or_default();
+
+    let line = before.matches('\n').count() + 1;
+    let column = before[line_start..].chars().count() + 1;
+
+    (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_columns, parse_rows, parse_table, parse_token, ParseError};
+
+    const INPUT: &str = "1 2
+3 x4";
+
+    #[test]
+    fn test_parse_token() {
+        let token = &INPUT[6..8];
+
+        assert_eq!(parse_token::<i64>(INPUT, &INPUT[0..1], "a number"), Ok(1));
+        assert_eq!(
+            parse_token::<i64>(INPUT, token, "a number"),
+            Err(ParseError::InvalidToken {
+                line: 2,
+                column: 3,
+                token: "x4".into(),
+                expected: "a number"
+            })
+        );
+    }
+
+    #[test]
+    fn test_missing_token() {
+        let line = INPUT.lines().next().unwrap();
+        let err = ParseError::missing(INPUT, line, "a third number");
+
+        assert_eq!((err.line(), err.column()), (1, 4));
+        assert_eq!(err.clone().offset_lines(2).line(), 3);
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 4: expected a third number, found nothing"
+        );
+    }
+
+    #[test]
+    fn test_parse_rows() {
+        assert_eq!(
+            parse_rows::<u8>("1 2 3\n\n4", "a number"),
+            Ok(vec![vec![1, 2, 3], vec![], vec![4]])
+        );
+        assert_eq!(
+            parse_rows::<u8>("1 2\n3 -4", "a number"),
+            Err(ParseError::InvalidToken {
+                line: 2,
+                column: 3,
+                token: "-4".into(),
+                expected: "a number"
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_table() {
+        assert_eq!(
+            parse_table::<i64, 2>("1   2\n-3 4", "a number"),
+            Ok(vec![[1, 2], [-3, 4]])
+        );
+    }
+
+    #[test]
+    fn test_parse_table_ragged() {
+        assert_eq!(
+            parse_table::<i64, 2>("1 2\n3", "a number"),
+            Err(ParseError::MissingToken {
+                line: 2,
+                column: 2,
+                expected: "a number"
+            })
+        );
+        assert_eq!(
+            parse_table::<i64, 2>("1 2 3", "a number"),
+            Err(ParseError::InvalidToken {
+                line: 1,
+                column: 5,
+                token: "3".into(),
+                expected: "end of line"
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_columns() {
+        assert_eq!(
+            parse_columns::<i64>("1 2 3\n4 5 6", 3, "a number"),
+            Ok(vec![vec![1, 4], vec![2, 5], vec![3, 6]])
+        );
+        assert_eq!(
+            parse_columns::<i64>("1 2 3\n4 5", 3, "a number"),
+            Err(ParseError::MissingToken {
+                line: 2,
+                column: 4,
+                expected: "a number"
+            })
+        );
+        assert_eq!(
+            parse_columns::<i64>("1 2\n3 4 5", 2, "a number"),
+            Err(ParseError::InvalidToken {
+                line: 2,
+                column: 5,
+                token: "5".into(),
+                expected: "end of line"
+            })
+        );
+    }
+}
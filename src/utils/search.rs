@@ -0,0 +1,382 @@
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// Everything a search learned about the nodes it reached.
+#[derive(Debug, Clone)]
+pub struct Search<N, C> {
+    order: Vec<N>,
+    distances: HashMap<N, C>,
+    predecessors: HashMap<N, N>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Search<N, C> {
+    fn new(start: N, zero: C) -> Self {
+        Search {
+            order: vec![],
+            distances: HashMap::from([(start, zero)]),
+            predecessors: HashMap::new(),
+        }
+    }
+
+    /// All reached nodes in the order they were visited, starting with the start node.
+    pub fn order(&self) -> &[N] {
+        &self.order
+    }
+
+    pub fn distances(&self) -> &HashMap<N, C> {
+        &self.distances
+    }
+
+    pub fn distance(&self, node: &N) -> Option<C> {
+        self.distances.get(node).copied()
+    }
+
+    pub fn predecessors(&self) -> &HashMap<N, N> {
+        &self.predecessors
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.distances.contains_key(node)
+    }
+
+    /// Reconstruct the path from the start node to `node` (both inclusive).
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        if !self.contains(node) {
+            return None;
+        }
+
+        let mut path = vec![node.clone()];
+        let mut current = node;
+
+        while let Some(previous) = self.predecessors.get(current) {
+            path.push(previous.clone());
+            current = previous;
+        }
+
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Breadth first search from `start`. Distances are the number of steps on a shortest path.
+pub fn bfs<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> Search<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut search = Search::new(start.clone(), 0);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(current) = queue.pop_front() {
+        let distance = search.distances[&current];
+
+        for next in successors(&current) {
+            if let Entry::Vacant(entry) = search.distances.entry(next.clone()) {
+                entry.insert(distance + 1);
+                search.predecessors.insert(next.clone(), current.clone());
+                queue.push_back(next);
+            }
+        }
+
+        search.order.push(current);
+    }
+
+    search
+}
+
+/// Depth first search from `start`. Distances are the depths in the resulting search tree, which
+/// are not necessarily the shortest ones.
+pub fn dfs<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> Search<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut search = Search {
+        order: vec![],
+        distances: HashMap::new(),
+        predecessors: HashMap::new(),
+    };
+    let mut stack = vec![(start, None)];
+
+    while let Some((current, predecessor)) = stack.pop() {
+        if search.distances.contains_key(&current) {
+            continue;
+        }
+
+        let distance = match &predecessor {
+            Some(previous) => search.distances[previous] + 1,
+            None => 0,
+        };
+        search.distances.insert(current.clone(), distance);
+        if let Some(previous) = predecessor {
+            search.predecessors.insert(current.clone(), previous);
+        }
+
+        let mut next = successors(&current)
+            .into_iter()
+            .filter(|next| !search.distances.contains_key(next))
+            .map(|next| (next, Some(current.clone())))
+            .collect::<Vec<_>>();
+        // visit successors in the order they were returned
+        next.reverse();
+        stack.extend(next);
+
+        search.order.push(current);
+    }
+
+    search
+}
+
+/// Dijkstra's algorithm from `start`, where `successors` returns the neighbours of a node
+/// together with the (non-negative) cost to move there.
+pub fn dijkstra<N, C, I>(start: N, mut successors: impl FnMut(&N) -> I) -> Search<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut search = Search::new(start.clone(), C::default());
+    // the heap only holds indices into `nodes`, so nodes do not have to be `Ord`
+    let mut nodes = vec![start];
+    let mut heap = BinaryHeap::from([Reverse((C::default(), 0))]);
+
+    while let Some(Reverse((cost, index))) = heap.pop() {
+        let current = nodes[index].clone();
+        if search.distances[&current] < cost {
+            continue;
+        }
+
+        for (next, step) in successors(&current) {
+            let next_cost = cost + step;
+
+            if search
+                .distance(&next)
+                .is_some_and(|known| known <= next_cost)
+            {
+                continue;
+            }
+
+            search.distances.insert(next.clone(), next_cost);
+            search.predecessors.insert(next.clone(), current.clone());
+            heap.push(Reverse((next_cost, nodes.len())));
+            nodes.push(next);
+        }
+
+        search.order.push(current);
+    }
+
+    search
+}
+
+/// A* search from `start` to the first node satisfying `is_goal`. `heuristic` must never
+/// overestimate the remaining cost. Returns the path (both ends inclusive) and its cost.
+pub fn astar<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut search = Search::new(start.clone(), C::default());
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), C::default(), 0))]);
+    let mut nodes = vec![start];
+
+    while let Some(Reverse((_, cost, index))) = heap.pop() {
+        let current = nodes[index].clone();
+        if search.distances[&current] < cost {
+            continue;
+        }
+
+        if is_goal(&current) {
+            let path = search.path_to(&current)?;
+            return Some((path, cost));
+        }
+
+        for (next, step) in successors(&current) {
+            let next_cost = cost + step;
+
+            if search
+                .distance(&next)
+                .is_some_and(|known| known <= next_cost)
+            {
+                continue;
+            }
+
+            search.distances.insert(next.clone(), next_cost);
+            search.predecessors.insert(next.clone(), current.clone());
+            heap.push(Reverse((
+                next_cost + heuristic(&next),
+                next_cost,
+                nodes.len(),
+            )));
+            nodes.push(next);
+        }
+    }
+
+    None
+}
+
+/// Count all distinct paths from `start` to nodes satisfying `is_goal`. A path ends at the first
+/// goal it reaches.
+///
+/// The graph reachable from `start` has to be acyclic, otherwise there are infinitely many paths.
+pub fn count_paths<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> usize
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    fn count<N, I>(
+        current: &N,
+        successors: &mut impl FnMut(&N) -> I,
+        is_goal: &mut impl FnMut(&N) -> bool,
+        known: &mut HashMap<N, usize>,
+    ) -> usize
+    where
+        N: Clone + Eq + Hash,
+        I: IntoIterator<Item = N>,
+    {
+        if let Some(paths) = known.get(current) {
+            return *paths;
+        }
+
+        let paths = if is_goal(current) {
+            1
+        } else {
+            successors(current)
+                .into_iter()
+                .map(|next| count(&next, successors, is_goal, known))
+                .sum()
+        };
+
+        known.insert(current.clone(), paths);
+        paths
+    }
+
+    count(&start, &mut successors, &mut is_goal, &mut HashMap::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{astar, bfs, count_paths, dfs, dijkstra};
+    use crate::utils::{Coord, Grid};
+
+    const MAZE: &str = "..#.
+.##.
+....";
+
+    fn open_neighbors(grid: &Grid<char>, coord: Coord) -> Vec<Coord> {
+        coord
+            .neighbors4()
+            .filter(|n| grid.get(*n) == Some(&'.'))
+            .collect()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let grid = Grid::from_str(MAZE, |c| c).unwrap();
+        let search = bfs(Coord(0, 0), |c| open_neighbors(&grid, *c));
+
+        assert_eq!(search.distance(&Coord(3, 0)), Some(7));
+        assert_eq!(search.distance(&Coord(2, 0)), None);
+        assert_eq!(search.order().len(), 9);
+        assert_eq!(
+            search.path_to(&Coord(3, 1)),
+            Some(vec![
+                Coord(0, 0),
+                Coord(0, 1),
+                Coord(0, 2),
+                Coord(1, 2),
+                Coord(2, 2),
+                Coord(3, 2),
+                Coord(3, 1),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_dfs() {
+        let successors = |n: &u32| if *n < 3 { vec![n + 1, n + 2] } else { vec![] };
+        let search = dfs(0, successors);
+
+        assert_eq!(search.order(), &[0, 1, 2, 3, 4]);
+        assert_eq!(search.distance(&4), Some(3));
+        assert_eq!(search.path_to(&4), Some(vec![0, 1, 2, 4]));
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        // a -> b is expensive, the detour over c is cheaper
+        let successors = |n: &char| match n {
+            'a' => vec![('b', 10), ('c', 1)],
+            'c' => vec![('b', 2), ('d', 7)],
+            'b' => vec![('d', 1)],
+            _ => vec![],
+        };
+        let search = dijkstra('a', successors);
+
+        assert_eq!(search.distance(&'b'), Some(3));
+        assert_eq!(search.distance(&'d'), Some(4));
+        assert_eq!(search.path_to(&'d'), Some(vec!['a', 'c', 'b', 'd']));
+    }
+
+    #[test]
+    fn test_astar() {
+        let grid = Grid::from_str(MAZE, |c| c).unwrap();
+        let goal = Coord(3, 0);
+
+        let (path, cost) = astar(
+            Coord(0, 0),
+            |c| open_neighbors(&grid, *c).into_iter().map(|n| (n, 1)),
+            |c| c.manhattan(goal),
+            |c| *c == goal,
+        )
+        .unwrap();
+
+        assert_eq!(cost, 7);
+        assert_eq!(path.len(), 8);
+        assert_eq!(path.last(), Some(&goal));
+
+        let unreachable = astar(
+            Coord(0, 0),
+            |c| open_neighbors(&grid, *c).into_iter().map(|n| (n, 1)),
+            |_| 0,
+            |c| *c == Coord(2, 0),
+        );
+        assert_eq!(unreachable, None);
+    }
+
+    #[test]
+    fn test_count_paths() {
+        // every step goes right or down in a 3x3 grid
+        let successors = |Coord(x, y): &Coord| {
+            [Coord(x + 1, *y), Coord(*x, y + 1)]
+                .into_iter()
+                .filter(|c| c.is_within((3, 3)))
+        };
+
+        assert_eq!(
+            count_paths(Coord(0, 0), successors, |c| *c == Coord(2, 2)),
+            6
+        );
+        assert_eq!(
+            count_paths(Coord(2, 2), successors, |c| *c == Coord(2, 2)),
+            1
+        );
+        assert_eq!(
+            count_paths(Coord(2, 2), successors, |c| *c == Coord(0, 0)),
+            0
+        );
+    }
+}
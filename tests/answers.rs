@@ -0,0 +1,51 @@
+//! Checks every day against the accepted answers in `answers/2024.toml`.
+//!
+//! These run as integration tests, so the solutions are compiled without their `cfg(test)` debug
+//! output. Days without an input in `input/2024` are skipped.
+
+use aoc_2024::{
+    answers::{self, Answers, Verdict},
+    registry,
+};
+
+fn check_day(day: u32) {
+    let answers = Answers::load(answers::ANSWERS_PATH).unwrap();
+    let solution = registry::find(day).unwrap();
+
+    for (part, verdict) in (1..).zip(answers::verify(solution, &answers).unwrap()) {
+        match verdict {
+            Verdict::Correct => {}
+            Verdict::MissingInput => eprintln!("Day {day}: skipped, no input"),
+            Verdict::Unknown { actual } => {
+                eprintln!("Day {day} - Part {part}: no accepted answer for {actual}")
+            }
+            verdict => panic!("Day {day} - Part {part}: {verdict:?}"),
+        }
+    }
+}
+
+// one test per day, so the slow days run in parallel
+macro_rules! answer_tests {
+    ($($name:ident => $day:literal),* $(,)?) => {
+        $(
+            #[test]
+            fn $name() {
+                check_day($day);
+            }
+        )*
+    };
+}
+
+answer_tests! {
+    test_day_01 => 1,
+    test_day_02 => 2,
+    test_day_03 => 3,
+    test_day_04 => 4,
+    test_day_05 => 5,
+    test_day_06 => 6,
+    test_day_07 => 7,
+    test_day_08 => 8,
+    test_day_09 => 9,
+    test_day_10 => 10,
+    test_day_11 => 11,
+}